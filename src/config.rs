@@ -1,8 +1,14 @@
-use serde_json::{from_str, Value};
+use serde_json::{from_str, Map, Value};
 use std::error::Error;
 
+use crate::{
+    source::DuplicatePolicy,
+    validate::{KeyPolicy, Policy},
+};
+
 pub const CFG_JSON: &str = r#"{
     "sheetName":"",
+    "target": "android",
     "tagName": "Android tag",
     "defaultLang":"en",
     "langMap": {
@@ -36,7 +42,28 @@ pub const CFG_JSON: &str = r#"{
     "ignoreFolder": [
         "build",
         "mainland"
-    ]
+    ],
+    "propertiesBaseName": "messages",
+    "propertiesEncoding": "ascii",
+    "commentName": "",
+    "xliffVersion": "1.2",
+    "resxBaseName": "Strings",
+    "placeholderCheck": "off",
+    "aaptCheck": "off",
+    "markupCheck": "off",
+    "keyCheck": "off",
+    "duplicateTag": "last",
+    "duplicateString": "off",
+    "maxLengthName": "",
+    "maxLengthRatio": {},
+    "lengthCheck": "off",
+    "translatableName": "",
+    "formattedName": "",
+    "rowFilters": [],
+    "skipHidden": false,
+    "skipStrikethrough": false,
+    "dateFormat": "%Y-%m-%d",
+    "mergedColumns": []
 }"#;
 
 /**
@@ -45,6 +72,7 @@ pub const CFG_JSON: &str = r#"{
 #[derive(Debug, PartialEq)]
 pub struct ParsedCfg {
     pub sheet_name: String,                 // 表名
+    pub target: String,                     // 输出目标 android/harmony/properties/resx/po/xliff
     pub default_lang: String,               // 默认语言
     pub reset: bool,                        // 是否替换所有
     pub disable_escape: bool,               // 是否禁用转义
@@ -52,11 +80,36 @@ pub struct ParsedCfg {
     pub regex: String,                      // 正则表达式
     pub tag_name: String,                // 标签列名称
     pub tag_index: u32,                  // 标签序号 excel中的序号
+    pub comment_name: String,            // 备注列名称，为空时不读取
+    pub comment_index: Option<u32>,      // 备注列序号 excel中的序号
+    pub max_length_name: String,         // 最大长度列名称，为空时不读取
+    pub max_length_index: Option<u32>,   // 最大长度列序号 excel中的序号
+    pub translatable_name: String,       // 是否需要翻译的标记列名称，为空时不读取
+    pub translatable_index: Option<u32>, // 是否需要翻译的标记列序号 excel中的序号
+    pub formatted_name: String,          // 是否为格式化字符串的标记列名称，为空时不读取
+    pub formatted_index: Option<u32>,    // 是否为格式化字符串的标记列序号 excel中的序号
     
     pub lang_map: Vec<(String, String)>, // 语言名称 zh - 简体中文
     pub lang_index_map: Vec<(String, u32)>, // 语言名称 zh - 0（excel中的序号）
     pub escape_only: Vec<(String, String)>, // 只需要转义这部分内容，没配置就转义全部
     pub ignore_folder: Vec<String>,         // 忽略的文件夹
+    pub properties_base_name: String,       // properties文件名前缀 messages_zh.properties
+    pub properties_encoding: String,        // properties编码 ascii(\uXXXX) 或 utf-8
+    pub xliff_version: String,              // 导出的XLIFF版本 1.2 或 2.0
+    pub resx_base_name: String,             // resx文件名前缀 Strings.zh-TW.resx
+    pub placeholder_check: String,          // 占位符检查 off/warn/error
+    pub aapt_check: String,                 // aapt2资源规则检查 off/warn/error，只对android生效
+    pub markup_check: String,               // 标记标签检查 off/warn/error
+    pub key_check: String,                  // 标签名称检查 off/warn/error/skip/sanitize，只对android生效
+    pub duplicate_tag: String,              // 重复标签的处理 first/last/abort
+    pub duplicate_string: String,           // strings.xml中重复name的处理 off/warn/dedupe
+    pub max_length_ratio: Vec<(String, f64)>, // 相对默认语言长度的上限倍数 de - 1.5，* 对应所有语言
+    pub length_check: String,               // 长度检查 off/warn/error
+    pub row_filters: Vec<String>,           // 行筛选条件，如 Platform contains Android，全部满足时才读取
+    pub skip_hidden: bool,                  // 是否跳过隐藏的行和列，只对xlsx生效
+    pub skip_strikethrough: bool,           // 是否跳过删除线格式的单元格，只对xlsx生效
+    pub date_format: String,                // 日期单元格转换为文本的格式，支持 %Y %m %d %H %M %S
+    pub merged_columns: Vec<String>,        // 合并单元格的值填充到区域内每一行的列名称，* 对应所有列，只对xlsx生效
 }
 
 impl ParsedCfg {
@@ -70,6 +123,12 @@ impl ParsedCfg {
             .unwrap_or("")
             .to_string();
 
+        let target = json_obj
+            .get("target")
+            .and_then(Value::as_str)
+            .unwrap_or("android")
+            .to_string();
+
         let tag_name = json_obj
             .get("tagName")
             .and_then(Value::as_str)
@@ -129,8 +188,122 @@ impl ParsedCfg {
             })
             .unwrap_or_default();    
 
+        let properties_base_name = json_obj
+            .get("propertiesBaseName")
+            .and_then(Value::as_str)
+            .unwrap_or("messages")
+            .to_string();
+
+        let properties_encoding = json_obj
+            .get("propertiesEncoding")
+            .and_then(Value::as_str)
+            .unwrap_or("ascii")
+            .to_string();
+
+        let comment_name = json_obj
+            .get("commentName")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+
+        let xliff_version = json_obj
+            .get("xliffVersion")
+            .and_then(Value::as_str)
+            .unwrap_or("1.2")
+            .to_string();
+
+        let resx_base_name = json_obj
+            .get("resxBaseName")
+            .and_then(Value::as_str)
+            .unwrap_or("Strings")
+            .to_string();
+
+        let placeholder_check = read_choice(json_obj, "placeholderCheck", "off", &Policy::VALUES)?;
+
+        let aapt_check = read_choice(json_obj, "aaptCheck", "off", &Policy::VALUES)?;
+
+        let markup_check = read_choice(json_obj, "markupCheck", "off", &Policy::VALUES)?;
+
+        let key_check = read_choice(json_obj, "keyCheck", "off", &KeyPolicy::VALUES)?;
+
+        let duplicate_tag =
+            read_choice(json_obj, "duplicateTag", "last", &DuplicatePolicy::VALUES)?;
+
+        let duplicate_string = json_obj
+            .get("duplicateString")
+            .and_then(Value::as_str)
+            .unwrap_or("off")
+            .to_string();
+
+        let max_length_name = json_obj
+            .get("maxLengthName")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+
+        let max_length_ratio = json_obj
+            .get("maxLengthRatio")
+            .and_then(Value::as_object)
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_f64().map(|ratio| (k.clone(), ratio)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let length_check = read_choice(json_obj, "lengthCheck", "off", &Policy::VALUES)?;
+
+        let translatable_name = json_obj
+            .get("translatableName")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+
+        let formatted_name = json_obj
+            .get("formattedName")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+
+        let row_filters = json_obj
+            .get("rowFilters")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let skip_hidden = json_obj
+            .get("skipHidden")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let skip_strikethrough = json_obj
+            .get("skipStrikethrough")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let date_format = json_obj
+            .get("dateFormat")
+            .and_then(Value::as_str)
+            .unwrap_or("%Y-%m-%d")
+            .to_string();
+
+        let merged_columns = json_obj
+            .get("mergedColumns")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Ok(ParsedCfg {
             sheet_name,
+            target,
             tag_name,
             default_lang,
             lang_map,
@@ -140,12 +313,71 @@ impl ParsedCfg {
             replace_blank_with_default,
             regex,
             ignore_folder,
+            properties_base_name,
+            properties_encoding,
+            comment_name,
+            xliff_version,
+            resx_base_name,
+            placeholder_check,
+            aapt_check,
+            markup_check,
+            key_check,
+            duplicate_tag,
+            duplicate_string,
+            max_length_name,
+            max_length_ratio,
+            length_check,
+            translatable_name,
+            formatted_name,
+            row_filters,
+            skip_hidden,
+            skip_strikethrough,
+            date_format,
+            merged_columns,
             tag_index: 0,           // 默认值
             lang_index_map: vec![], // 默认值
+            comment_index: None,    // 默认值
+            max_length_index: None, // 默认值
+            translatable_index: None, // 默认值
+            formatted_index: None,  // 默认值
         })
     }
 }
 
+/// Android语言目录名转换为 语言_地区 格式，如 zh-rTW -> zh_TW
+pub fn locale_code(lang: &str) -> String {
+    lang.replace("-r", "_")
+}
+
+/// Android语言目录名转换为BCP 47语言标签，如 zh-rTW -> zh-TW
+pub fn language_tag(lang: &str) -> String {
+    lang.replace("-r", "-")
+}
+
+/// 读取只能取固定值的字段，未配置或为空时使用默认值，不在可选值中时返回错误
+fn read_choice(
+    json_obj: &Map<String, Value>,
+    key: &str,
+    default: &str,
+    choices: &[&str],
+) -> Result<String, Box<dyn Error>> {
+    let value = json_obj
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(default);
+    if !choices.contains(&value) {
+        return Err(format!(
+            "Invalid '{}' value: {:?}, expected one of {}",
+            key,
+            value,
+            choices.join("/")
+        )
+        .into());
+    }
+    Ok(value.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +390,17 @@ mod tests {
         println!("json: {}", json_data);
         println!("parsed--->{:?}", parsed_config);
     }
+
+    #[test]
+    fn test_invalid_choice() {
+        let cfg = r#"{"tagName":"tag","langMap":{},"escapeOnly":{},"placeholderCheck":"erorr"}"#;
+        let err = ParsedCfg::from_json(cfg).unwrap_err();
+        assert!(err.to_string().contains("placeholderCheck"));
+        let cfg = r#"{"tagName":"tag","langMap":{},"escapeOnly":{},"aaptCheck":""}"#;
+        assert_eq!(ParsedCfg::from_json(cfg).unwrap().aapt_check, "off");
+        let cfg = r#"{"tagName":"tag","langMap":{},"escapeOnly":{},"keyCheck":"rename"}"#;
+        assert!(ParsedCfg::from_json(cfg).is_err());
+        let cfg = r#"{"tagName":"tag","langMap":{},"escapeOnly":{},"duplicateTag":"keep"}"#;
+        assert!(ParsedCfg::from_json(cfg).is_err());
+    }
 }
@@ -0,0 +1,214 @@
+use std::{error::Error, fs, path::Path};
+
+use crate::{
+    config::ParsedCfg,
+    handoff::{self, SkippedTags, TagRow},
+};
+use quick_xml::escape::escape;
+use serde_json::{json, Value};
+
+/// 单个语言的翻译覆盖情况
+#[derive(Debug, Default, PartialEq)]
+pub struct LangCoverage {
+    pub lang: String,
+    pub name: String,      // 表头中的语言名称
+    pub total: usize,      // 默认语言有内容、且需要翻译的条目数
+    pub translated: usize, // 已翻译，且与默认语言不同
+    pub identical: usize,  // 与默认语言相同
+    pub filled: usize,     // 为空的条目中，写入时使用默认语言替换的数量
+    pub blank: usize,      // 为空，缺少翻译
+}
+
+impl LangCoverage {
+    /// 覆盖率，已翻译和与默认语言相同的条目占比
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        (self.translated + self.identical) as f64 * 100.0 / self.total as f64
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "lang": self.lang,
+            "name": self.name,
+            "total": self.total,
+            "translated": self.translated,
+            "identical": self.identical,
+            "filledFromDefault": self.filled,
+            "blank": self.blank,
+            "percent": (self.percent() * 10.0).round() / 10.0,
+        })
+    }
+}
+
+/**
+ * 统计各语言的翻译覆盖情况，输出到终端，并在out_dir下生成 coverage.json 和 coverage.html
+ * @param cfg_json 配置JSON
+ * @param source_path 数据来源，XML所在模块文件夹，或Excel/CSV文件
+ * @param out_dir 报告输出文件夹，为空时只输出到终端
+ * @return 各语言的覆盖情况
+ */
+pub fn report(
+    cfg_json: &str,
+    source_path: &str,
+    out_dir: &str,
+) -> Result<Vec<LangCoverage>, Box<dyn Error>> {
+    let (parsed_cfg, tag_rows, skipped) = handoff::read_tag_rows(cfg_json, source_path)?;
+    let coverages = compute(&parsed_cfg, &tag_rows, &skipped);
+    println!("{}", format_table(&coverages));
+
+    if !out_dir.is_empty() {
+        fs::create_dir_all(out_dir)?;
+        let json_path = Path::new(out_dir).join("coverage.json");
+        let json = Value::Array(coverages.iter().map(LangCoverage::to_json).collect());
+        fs::write(&json_path, serde_json::to_string_pretty(&json)? + "\n")?;
+        let html_path = Path::new(out_dir).join("coverage.html");
+        fs::write(&html_path, format_html(source_path, &coverages))?;
+        println!(
+            "写入覆盖率报告: {}, {}",
+            json_path.display(),
+            html_path.display()
+        );
+    }
+    Ok(coverages)
+}
+
+/// 统计各语言的覆盖情况，默认语言为空和不需要翻译的条目不计入
+/// 为空的条目即使写入时会使用默认语言替换，也计为缺少翻译
+fn compute(
+    parsed_cfg: &ParsedCfg,
+    tag_rows: &[TagRow],
+    skipped: &SkippedTags,
+) -> Vec<LangCoverage> {
+    let default_pos = parsed_cfg
+        .lang_map
+        .iter()
+        .position(|(lang, _)| *lang == parsed_cfg.default_lang);
+
+    parsed_cfg
+        .lang_map
+        .iter()
+        .enumerate()
+        .map(|(pos, (lang, name))| {
+            let mut coverage = LangCoverage {
+                lang: lang.clone(),
+                name: name.clone(),
+                ..Default::default()
+            };
+            for (tag, values) in tag_rows {
+                let default_value = default_pos.map(|p| values[p].trim()).unwrap_or("");
+                if default_value.is_empty() || skipped[pos].contains(tag) {
+                    continue;
+                }
+                coverage.total += 1;
+                let value = values[pos].trim();
+                if value.is_empty() {
+                    coverage.blank += 1;
+                    if parsed_cfg.replace_blank_with_default {
+                        coverage.filled += 1;
+                    }
+                } else if value == default_value {
+                    coverage.identical += 1;
+                } else {
+                    coverage.translated += 1;
+                }
+            }
+            coverage
+        })
+        .collect()
+}
+
+/// 生成终端表格
+fn format_table(coverages: &[LangCoverage]) -> String {
+    let mut table = format!(
+        "{:<10} {:>8} {:>10} {:>10} {:>8} {:>8} {:>8}\n",
+        "lang", "total", "translated", "identical", "filled", "blank", "percent"
+    );
+    for c in coverages {
+        table.push_str(&format!(
+            "{:<10} {:>8} {:>10} {:>10} {:>8} {:>8} {:>7.1}%\n",
+            c.lang,
+            c.total,
+            c.translated,
+            c.identical,
+            c.filled,
+            c.blank,
+            c.percent()
+        ));
+    }
+    table
+}
+
+/// 生成独立的HTML页面
+fn format_html(source_path: &str, coverages: &[LangCoverage]) -> String {
+    let mut rows = String::new();
+    for c in coverages {
+        rows.push_str(&format!(
+            "      <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><div class=\"bar\"><span style=\"width:{:.1}%\"></span></div>{:.1}%</td></tr>\n",
+            escape(c.lang.as_str()),
+            escape(c.name.as_str()),
+            c.total,
+            c.translated,
+            c.identical,
+            c.filled,
+            c.blank,
+            c.percent(),
+            c.percent()
+        ));
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>翻译覆盖率</title>
+  <style>
+    body {{ font-family: sans-serif; margin: 24px; }}
+    table {{ border-collapse: collapse; }}
+    th, td {{ border: 1px solid #ccc; padding: 4px 12px; text-align: right; }}
+    th:nth-child(-n+2), td:nth-child(-n+2) {{ text-align: left; }}
+    .bar {{ display: inline-block; width: 120px; height: 10px; margin-right: 8px; background: #eee; }}
+    .bar span {{ display: block; height: 100%; background: #4caf50; }}
+  </style>
+</head>
+<body>
+  <h1>翻译覆盖率</h1>
+  <p>{}</p>
+  <table>
+    <thead>
+      <tr><th>lang</th><th>name</th><th>total</th><th>translated</th><th>identical</th><th>filled from default</th><th>blank</th><th>percent</th></tr>
+    </thead>
+    <tbody>
+{}    </tbody>
+  </table>
+</body>
+</html>
+"#,
+        escape(source_path),
+        rows
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_compute() {
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","fr":"French"},"escapeOnly":{},"translatableName":"Translatable","replaceBlankWithDefault":true}"#;
+        let csv = "tag,English,French,Translatable\na,Hello,Bonjour,\nb,OK,OK,\nc,Save,,\nd,,,\napp_id,com.example,,false\n";
+        let (parsed_cfg, mut source) = test_util::csv_source(cfg, csv);
+        let tag_rows = handoff::read_source_tag_rows(&parsed_cfg, &mut source).unwrap();
+        let skipped = handoff::read_skipped_tags(&parsed_cfg, "", &mut source).unwrap();
+        let coverages = compute(&parsed_cfg, &tag_rows, &skipped);
+        let fr = coverages.iter().find(|c| c.lang == "fr").unwrap();
+        // 不需要翻译的app_id不计入，写入时会使用默认语言替换的c仍计为缺少翻译
+        assert_eq!(
+            (fr.total, fr.translated, fr.identical, fr.blank, fr.filled),
+            (3, 1, 1, 1, 1)
+        );
+        assert!((fr.percent() - 66.666).abs() < 0.01);
+    }
+}
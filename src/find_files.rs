@@ -79,4 +79,96 @@ pub fn find_target_folder(input: &str, target: &str, ignore: &[&str]) -> Option<
             }
         })
         .next()
-}
\ No newline at end of file
+}
+
+/**
+ * 收集所有目标文件夹，找到后不再查找其子目录
+ * @param input 输入路径
+ * @param target 目标文件夹名称
+ * @param ignore 忽略的文件夹
+ * @return 按路径排序的文件夹
+ */
+pub fn collect_target_folders(input: &str, target: &str, ignore: &[&str]) -> Vec<String> {
+    let path = Path::new(input);
+    if !path.is_dir() || ignore.iter().any(|&x| input.contains(x)) {
+        return Vec::new();
+    }
+    if path.file_name().unwrap_or_default() == target {
+        return vec![input.to_string()];
+    }
+    let mut folders: Vec<String> = read_dir(path)
+        .ok()
+        .into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| entry.path().to_str().map(|p| p.to_string()))
+        .flat_map(|entry_path| collect_target_folders(&entry_path, target, ignore))
+        .collect();
+    folders.sort();
+    folders
+}
+
+/**
+ * 收集子目录中指定相对路径的目标文件
+ * 如 resources/<locale>/element/string.json
+ * @param path 路径
+ * @param sub_path 子目录下的相对路径
+ * @param target 目标文件名称
+ * @return (子目录名称, 文件路径)
+ */
+pub fn collect_nested_target_files(
+    path: &str,
+    sub_path: &str,
+    target: &str,
+) -> Vec<(String, String)> {
+    let path = Path::new(path);
+    if !path.is_dir() {
+        return Vec::new(); // 当前路径不是文件夹，返回空向量
+    }
+
+    read_dir(path)
+        .ok()
+        .into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let entry_path = entry.path();
+            if !entry_path.is_dir() {
+                return None; // 不是文件夹，跳过
+            }
+            let dir_name = entry.file_name().to_str()?.to_owned();
+            let file_path = entry_path.join(sub_path).join(target);
+            if file_path.is_file() {
+                println!("符合条件的文件: {}", file_path.display());
+                Some((dir_name, file_path.to_str()?.to_string()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_collect_nested_target_files() {
+        let dir = std::env::temp_dir().join("excel_to_xml_test_nested");
+        fs::remove_dir_all(&dir).ok();
+        for locale in ["base", "zh_TW", "rawfile"] {
+            fs::create_dir_all(dir.join(locale).join("element")).unwrap();
+        }
+        fs::write(dir.join("base/element/string.json"), "{}").unwrap();
+        fs::write(dir.join("zh_TW/element/string.json"), "{}").unwrap();
+        fs::write(dir.join("string.json"), "{}").unwrap();
+
+        let mut files =
+            collect_nested_target_files(dir.to_str().unwrap(), "element", "string.json");
+        files.sort();
+        let locales: Vec<&str> = files.iter().map(|(locale, _)| locale.as_str()).collect();
+        assert_eq!(locales, vec!["base", "zh_TW"]);
+        assert!(files[1].1.ends_with("zh_TW/element/string.json"));
+        assert!(collect_nested_target_files("/nonexistent", "element", "string.json").is_empty());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
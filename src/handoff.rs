@@ -0,0 +1,282 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use crate::{
+    config::ParsedCfg,
+    read_excel,
+    source::{self, SourceRow, TranslationSource},
+    target::{LangEntries, LocalizationTarget},
+    write_xlsx,
+    write_xml::AndroidTarget,
+};
+
+/// 标签及各语言的值，值的顺序与lang_map一致
+pub type TagRow = (String, Vec<String>);
+
+/// 导出时的快照，标签 - 语言 - 值，合并时用于判断主表和返回的表格是否有修改
+pub type BaseSnapshot = BTreeMap<String, BTreeMap<String, String>>;
+
+/// 每种语言不需要翻译的标签，顺序与lang_map一致
+pub type SkippedTags = Vec<HashSet<String>>;
+
+/**
+ * 导出缺失或空白翻译的条目，交给翻译人员补充
+ * 只包含默认语言有内容、但至少一种语言缺失的标签，列为 标签、默认语言 和 有缺失的语言
+ * 标记为不需要翻译的标签不算缺失
+ * 已有的翻译原样保留，缺失的单元格为空，翻译完成后可直接用于 update
+ * 同时在输出文件旁写入导出时的快照 <输出文件>.base.json，供合并时使用
+ * @param cfg_json 配置JSON
+ * @param source_path 数据来源，XML所在模块文件夹，或Excel/CSV文件
+ * @param out_path 输出文件路径，.csv 后缀输出CSV，其余输出xlsx
+ */
+pub fn export_missing(
+    cfg_json: &str,
+    source_path: &str,
+    out_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let (parsed_cfg, tag_rows, skipped) = read_tag_rows(cfg_json, source_path)?;
+    let rows = missing_rows(&parsed_cfg, &tag_rows, &skipped);
+    let sheet_name = if parsed_cfg.sheet_name.is_empty() {
+        "Sheet1"
+    } else {
+        parsed_cfg.sheet_name.as_str()
+    };
+    write_xlsx::write_table(out_path, sheet_name, &rows)?;
+    println!("写入待翻译文件: {}, 共{}条", out_path, rows.len() - 1);
+    let base_path = snapshot_path(out_path);
+    fs::write(
+        &base_path,
+        serde_json::to_string_pretty(&snapshot(&parsed_cfg, &rows))? + "\n",
+    )?;
+    println!("写入导出快照: {}", base_path);
+    Ok(())
+}
+
+/// 交接表格对应的快照路径
+pub fn snapshot_path(table_path: &str) -> String {
+    format!("{}.base.json", table_path)
+}
+
+/// 由交接表格生成快照，表头中的语言名称转换为语言
+fn snapshot(parsed_cfg: &ParsedCfg, rows: &[Vec<String>]) -> BaseSnapshot {
+    let langs: Vec<Option<&String>> = rows[0]
+        .iter()
+        .map(|name| {
+            parsed_cfg
+                .lang_map
+                .iter()
+                .find(|(_, lang_name)| lang_name == name)
+                .map(|(lang, _)| lang)
+        })
+        .collect();
+    rows.iter()
+        .skip(1)
+        .map(|row| {
+            let values = langs
+                .iter()
+                .zip(row)
+                .filter_map(|(lang, value)| Some(((*lang)?.clone(), value.clone())))
+                .collect();
+            (row[0].clone(), values)
+        })
+        .collect()
+}
+
+/**
+ * 读取交接表格对应的快照
+ * @param table_path 交接表格路径
+ * @return 快照，文件不存在时返回None
+ */
+pub fn read_snapshot(table_path: &str) -> Result<Option<BaseSnapshot>, Box<dyn Error>> {
+    let base_path = snapshot_path(table_path);
+    if !Path::new(&base_path).is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&base_path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/**
+ * 按顺序读取所有标签及各语言的值，以及各语言不需要翻译的标签
+ * @param cfg_json 配置JSON
+ * @param source_path 数据来源，XML所在模块文件夹，或Excel/CSV文件
+ * @return 解析后的配置，标签及各语言的值，不需要翻译的标签
+ */
+pub fn read_tag_rows(
+    cfg_json: &str,
+    source_path: &str,
+) -> Result<(ParsedCfg, Vec<TagRow>, SkippedTags), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(source_path, cfg_json)?;
+    let mut source = source::open_source(source_path, &parsed_cfg)?;
+    let tag_rows = read_source_tag_rows(&parsed_cfg, source.as_mut())?;
+    let skipped = read_skipped_tags(&parsed_cfg, source_path, source.as_mut())?;
+    Ok((parsed_cfg, tag_rows, skipped))
+}
+
+/**
+ * 读取各语言不需要翻译的标签
+ * 表格中标记为不需要翻译的对所有语言生效；数据来源为XML所在模块时，同时使用strings.xml中的标记
+ * @param parsed_cfg 解析后的配置
+ * @param source_path 数据来源路径
+ * @param source 数据源
+ */
+pub fn read_skipped_tags(
+    parsed_cfg: &ParsedCfg,
+    source_path: &str,
+    source: &mut dyn TranslationSource,
+) -> Result<SkippedTags, Box<dyn Error>> {
+    let meta = read_excel::read_sheet_meta(source, parsed_cfg)?;
+    let files: HashMap<String, String> = if Path::new(source_path).is_dir() {
+        AndroidTarget
+            .discover(source_path, parsed_cfg)?
+            .into_iter()
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let empty_map = HashMap::new();
+    parsed_cfg
+        .lang_map
+        .iter()
+        .map(|(lang, _)| {
+            let mut skipped: HashSet<String> = meta
+                .translatable_map
+                .iter()
+                .filter(|(_, translatable)| !**translatable)
+                .map(|(tag, _)| tag.clone())
+                .collect();
+            if let Some(path) = files.get(lang) {
+                let entries = LangEntries {
+                    lang,
+                    tag_value_map: &empty_map,
+                    default_value_map: &empty_map,
+                    meta: &meta,
+                };
+                skipped.extend(AndroidTarget.skipped_tags(path, &entries, parsed_cfg)?);
+            }
+            Ok(skipped)
+        })
+        .collect()
+}
+
+/// 按数据源中的顺序读取，数据源中没有的语言列视为全部缺失
+pub fn read_source_tag_rows(
+    parsed_cfg: &ParsedCfg,
+    source: &mut dyn TranslationSource,
+) -> Result<Vec<TagRow>, Box<dyn Error>> {
+    let lang_indices: HashMap<&String, u32> = parsed_cfg
+        .lang_index_map
+        .iter()
+        .map(|(lang, index)| (lang, *index))
+        .collect();
+    let columns: Vec<u32> = lang_indices.values().copied().collect();
+
+    let mut tag_rows = Vec::new();
+    source.read_rows(parsed_cfg.tag_index, &columns, &mut |row: SourceRow| {
+        let values = parsed_cfg
+            .lang_map
+            .iter()
+            .map(|(lang, _)| {
+                lang_indices
+                    .get(lang)
+                    .and_then(|index| row.cells.get(index))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect();
+        tag_rows.push((row.tag, values));
+    })?;
+    Ok(tag_rows)
+}
+
+/**
+ * 筛选有缺失翻译的条目，生成交接表格
+ * @param parsed_cfg 解析后的配置
+ * @param tag_rows 标签及各语言的值
+ * @param skipped 各语言不需要翻译的标签
+ * @return 表格数据，第一行为表头
+ */
+fn missing_rows(
+    parsed_cfg: &ParsedCfg,
+    tag_rows: &[TagRow],
+    skipped: &SkippedTags,
+) -> Vec<Vec<String>> {
+    let lang_map = &parsed_cfg.lang_map;
+    let default_pos = lang_map
+        .iter()
+        .position(|(lang, _)| *lang == parsed_cfg.default_lang);
+    let default_pos = match default_pos {
+        Some(pos) => pos,
+        None => {
+            println!("langMap中未找到默认语言: {}", parsed_cfg.default_lang);
+            return vec![vec![parsed_cfg.tag_name.clone()]];
+        }
+    };
+
+    // 语言的值为空，且该语言需要翻译
+    let is_gap = |tag: &String, values: &[String], pos: usize| {
+        values[pos].trim().is_empty() && !skipped[pos].contains(tag)
+    };
+    // 默认语言为空的条目无需翻译
+    let gap_rows: Vec<&TagRow> = tag_rows
+        .iter()
+        .filter(|(_, values)| !values[default_pos].trim().is_empty())
+        .filter(|(tag, values)| (0..values.len()).any(|pos| is_gap(tag, values, pos)))
+        .collect();
+
+    // 默认语言列在前，其后为有缺失的语言列
+    let mut positions = vec![default_pos];
+    positions.extend((0..lang_map.len()).filter(|pos| {
+        *pos != default_pos
+            && gap_rows
+                .iter()
+                .any(|(tag, values)| is_gap(tag, values, *pos))
+    }));
+
+    let mut header = vec![parsed_cfg.tag_name.clone()];
+    header.extend(positions.iter().map(|pos| lang_map[*pos].1.clone()));
+    let mut rows = vec![header];
+    for (tag, values) in gap_rows {
+        let mut row = vec![tag.clone()];
+        row.extend(positions.iter().map(|pos| {
+            let value = &values[*pos];
+            if value.trim().is_empty() {
+                String::new()
+            } else {
+                value.clone()
+            }
+        }));
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_missing_rows() {
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","fr":"French","ja":"Japanese"},"escapeOnly":{},"translatableName":"Translatable"}"#;
+        let csv = "tag,English,French,Japanese,Translatable\ndone,Done,Fini,完了,\nno_default,,,,\nmissing_fr,Hello, ,こんにちは,\napp_id,com.example,,,false\n";
+        let (parsed_cfg, mut source) = test_util::csv_source(cfg, csv);
+        let tag_rows = read_source_tag_rows(&parsed_cfg, &mut source).unwrap();
+        let skipped = read_skipped_tags(&parsed_cfg, "", &mut source).unwrap();
+        assert!(skipped[1].contains("app_id"));
+        // 不需要翻译的app_id不算缺失
+        let rows = missing_rows(&parsed_cfg, &tag_rows, &skipped);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], vec!["tag", "English", "French"]);
+        assert_eq!(rows[1], vec!["missing_fr", "Hello", ""]);
+
+        let base = snapshot(&parsed_cfg, &rows);
+        assert_eq!(base["missing_fr"]["en"], "Hello");
+        assert_eq!(base["missing_fr"]["fr"], "");
+        assert!(!base["missing_fr"].contains_key("ja"));
+    }
+}
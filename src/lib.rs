@@ -1,9 +1,27 @@
 use calamine::Reader;
 
 mod config;
+mod target;
 mod read_excel;
+mod source;
 mod write_xml;
 mod find_files;
+mod read_po;
+mod write_po;
+mod write_properties;
+mod write_harmony;
+mod read_xliff;
+mod write_xliff;
+mod write_resx;
+mod write_xlsx;
+mod read_xml;
+mod handoff;
+mod xlsx_package;
+mod merge;
+mod coverage;
+mod validate;
+#[cfg(test)]
+mod test_util;
 
 /// 获取Excel文件中的工作表名称列表
 pub fn get_sheet_names(file_path: &str) -> Vec<String> {
@@ -21,7 +39,7 @@ pub fn get_default_cfg_json() -> String {
 
 /// 更新XML文件
 pub fn update(cfg_json: &str, excel_path: &str, xml_dir_path: &str) -> String {
-    match write_xml::update(&cfg_json, &excel_path, &xml_dir_path) {
+    match target::update(cfg_json, excel_path, xml_dir_path) {
         Ok(_) => "update success".to_string(),
         Err(e) => "更新失败:".to_owned() + &format!("{:?}", e),
     }
@@ -29,8 +47,108 @@ pub fn update(cfg_json: &str, excel_path: &str, xml_dir_path: &str) -> String {
 
 /// 快速更新XML文件
 pub fn quick_update(cfg_json: &str, excel_path: &str, xml_dir_path: &str) -> String {
-    match write_xml::quick_update(&cfg_json, &excel_path, &xml_dir_path) {
+    match target::quick_update(cfg_json, excel_path, xml_dir_path) {
         Ok(_) => "quick update success".to_string(),
         Err(e) => "更新失败:".to_owned() + &format!("{:?}", e),
     }
 }
+
+/// 导出PO文件
+pub fn export_po(cfg_json: &str, excel_path: &str, out_dir: &str) -> String {
+    match write_po::export(cfg_json, excel_path, out_dir) {
+        Ok(_) => "export po success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 使用PO文件更新XML文件
+pub fn update_from_po(cfg_json: &str, po_dir: &str, xml_dir_path: &str) -> String {
+    match read_po::update(cfg_json, po_dir, xml_dir_path) {
+        Ok(_) => "update from po success".to_string(),
+        Err(e) => "更新失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 导出Java properties文件
+pub fn export_properties(cfg_json: &str, excel_path: &str, out_dir: &str) -> String {
+    match write_properties::export(cfg_json, excel_path, out_dir) {
+        Ok(_) => "export properties success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 更新鸿蒙string.json文件
+pub fn update_harmony(cfg_json: &str, excel_path: &str, project_dir: &str) -> String {
+    match write_harmony::update(cfg_json, excel_path, project_dir) {
+        Ok(_) => "update harmony success".to_string(),
+        Err(e) => "更新失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 导出XLIFF文件
+pub fn export_xliff(cfg_json: &str, excel_path: &str, out_dir: &str) -> String {
+    match write_xliff::export(cfg_json, excel_path, out_dir) {
+        Ok(_) => "export xliff success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 使用XLIFF文件更新XML文件
+pub fn update_from_xliff(cfg_json: &str, xliff_dir: &str, xml_dir_path: &str) -> String {
+    match read_xliff::update(cfg_json, xliff_dir, xml_dir_path) {
+        Ok(_) => "update from xliff success".to_string(),
+        Err(e) => "更新失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 导出.NET resx文件
+pub fn export_resx(cfg_json: &str, excel_path: &str, out_dir: &str) -> String {
+    match write_resx::export(cfg_json, excel_path, out_dir) {
+        Ok(_) => "export resx success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 从已有的strings.xml生成Excel文件
+pub fn export_excel(cfg_json: &str, xml_dir_path: &str, excel_path: &str) -> String {
+    match read_xml::export_excel(cfg_json, xml_dir_path, excel_path) {
+        Ok(_) => "export excel success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 导出缺失翻译的条目
+pub fn export_missing(cfg_json: &str, source_path: &str, out_path: &str) -> String {
+    match handoff::export_missing(cfg_json, source_path, out_path) {
+        Ok(_) => "export missing success".to_string(),
+        Err(e) => "导出失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 将返回的翻译表格合并到主表
+pub fn merge_workbook(cfg_json: &str, master_path: &str, returned_path: &str) -> String {
+    match merge::merge(cfg_json, master_path, returned_path) {
+        Ok(report) => format!(
+            "merge success, updated: {}, conflicts: {}",
+            report.updated.len(),
+            report.conflicts.len()
+        ),
+        Err(e) => "合并失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 生成翻译覆盖率报告
+pub fn coverage_report(cfg_json: &str, source_path: &str, out_dir: &str) -> String {
+    match coverage::report(cfg_json, source_path, out_dir) {
+        Ok(_) => "coverage report success".to_string(),
+        Err(e) => "统计失败:".to_owned() + &format!("{:?}", e),
+    }
+}
+
+/// 检查Excel中的翻译，不写入文件
+pub fn validate(cfg_json: &str, excel_path: &str) -> String {
+    match validate::validate(cfg_json, excel_path) {
+        Ok(_) => "validate success".to_string(),
+        Err(e) => "检查失败:".to_owned() + &format!("{:?}", e),
+    }
+}
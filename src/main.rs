@@ -4,9 +4,27 @@ use std::{
 };
 
 mod config;
+mod coverage;
 mod find_files;
+mod handoff;
+mod merge;
 mod read_excel;
+mod read_po;
+mod read_xliff;
+mod read_xml;
+mod source;
+mod target;
+#[cfg(test)]
+mod test_util;
+mod validate;
+mod write_harmony;
+mod write_po;
+mod write_properties;
+mod write_resx;
+mod write_xliff;
+mod write_xlsx;
 mod write_xml;
+mod xlsx_package;
 
 fn main() {
     // 是否是debug模式
@@ -29,10 +47,19 @@ fn main() {
     // 读取XML所在模块路径
     let mut xml_dir_path = String::new();
 
-    let menu = "c:更新json或文件路径\ne:更新xlsx路径\nx:更新xml所在文件夹路径\nu:同步\nqu:快速同步（内存占用多一点）\ni:查看当前配置信息\nm:菜单\nq:退出";
+    let menu = "c:更新json或文件路径\ne:更新xlsx路径\nx:更新xml所在文件夹路径\nu:同步\nqu:快速同步（内存占用多一点）\npo:导出PO文件\nipo:从PO文件同步\npr:导出properties文件\nhm:同步鸿蒙string.json\nxf:导出XLIFF文件\nixf:从XLIFF文件同步\nrx:导出resx文件\nxe:从xml生成Excel文件\nmi:导出缺失翻译的条目\nmg:合并返回的翻译表格到主表\ncv:翻译覆盖率报告\nvd:检查翻译（不写入文件）\ni:查看当前配置信息\nm:菜单\nq:退出";
     let json_prompt = "请输入json或文件路径:";
-    let excel_prompt = "请输入Excel路径:";
+    let excel_prompt = "请输入Excel/CSV路径（或作为数据来源的XML所在模块路径）:";
     let xml_prompt = "请输入XML所在模块路径:";
+    let out_prompt = "请输入输出文件夹路径:";
+    let po_prompt = "请输入PO文件所在文件夹路径:";
+    let harmony_prompt = "请输入鸿蒙模块路径:";
+    let xliff_prompt = "请输入XLIFF文件所在文件夹路径:";
+    let excel_out_prompt = "请输入输出的Excel文件路径:";
+    let source_prompt = "请输入数据来源（XML所在模块路径或Excel/CSV路径）:";
+    let table_out_prompt = "请输入输出文件路径（.xlsx或.csv）:";
+    let returned_prompt = "请输入返回的翻译表格路径:";
+    let report_prompt = "请输入报告输出文件夹路径（为空时只输出到终端）:";
     println!("example_cfg: \n{}", config::CFG_JSON);
     println!("{}", menu);
     loop {
@@ -50,7 +77,7 @@ fn main() {
             "u" => {
                 // 统计耗时
                 let start_time = std::time::Instant::now();
-                match write_xml::update(&cfg_json, &excel_path, &xml_dir_path) {
+                match target::update(&cfg_json, &excel_path, &xml_dir_path) {
                     Ok(_) => println!("更新成功"),
                     Err(e) => println!("更新失败: {:?}", e),
                 }
@@ -60,13 +87,104 @@ fn main() {
             "qu" => {
                 // 统计耗时
                 let start_time = std::time::Instant::now();
-                match write_xml::quick_update(&cfg_json, &excel_path, &xml_dir_path) {
+                match target::quick_update(&cfg_json, &excel_path, &xml_dir_path) {
                     Ok(_) => println!("更新成功"),
                     Err(e) => println!("更新失败: {:?}", e),
                 }
                 let duration = start_time.elapsed();
                 println!("快速同步耗时: {:?}", duration);
             }
+            "po" => {
+                let out_dir = prompt_user_input(out_prompt);
+                match write_po::export(&cfg_json, &excel_path, &out_dir) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "ipo" => {
+                let po_dir = prompt_user_input(po_prompt);
+                let start_time = std::time::Instant::now();
+                match read_po::update(&cfg_json, &po_dir, &xml_dir_path) {
+                    Ok(_) => println!("更新成功"),
+                    Err(e) => println!("更新失败: {:?}", e),
+                }
+                let duration = start_time.elapsed();
+                println!("同步耗时: {:?}", duration);
+            }
+            "pr" => {
+                let out_dir = prompt_user_input(out_prompt);
+                match write_properties::export(&cfg_json, &excel_path, &out_dir) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "hm" => {
+                let project_dir = prompt_user_input(harmony_prompt);
+                let start_time = std::time::Instant::now();
+                match write_harmony::update(&cfg_json, &excel_path, &project_dir) {
+                    Ok(_) => println!("更新成功"),
+                    Err(e) => println!("更新失败: {:?}", e),
+                }
+                let duration = start_time.elapsed();
+                println!("同步耗时: {:?}", duration);
+            }
+            "xf" => {
+                let out_dir = prompt_user_input(out_prompt);
+                match write_xliff::export(&cfg_json, &excel_path, &out_dir) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "ixf" => {
+                let xliff_dir = prompt_user_input(xliff_prompt);
+                let start_time = std::time::Instant::now();
+                match read_xliff::update(&cfg_json, &xliff_dir, &xml_dir_path) {
+                    Ok(_) => println!("更新成功"),
+                    Err(e) => println!("更新失败: {:?}", e),
+                }
+                let duration = start_time.elapsed();
+                println!("同步耗时: {:?}", duration);
+            }
+            "rx" => {
+                let out_dir = prompt_user_input(out_prompt);
+                match write_resx::export(&cfg_json, &excel_path, &out_dir) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "xe" => {
+                let out_path = prompt_user_input(excel_out_prompt);
+                match read_xml::export_excel(&cfg_json, &xml_dir_path, &out_path) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "mi" => {
+                let source_path = prompt_user_input(source_prompt);
+                let out_path = prompt_user_input(table_out_prompt);
+                match handoff::export_missing(&cfg_json, &source_path, &out_path) {
+                    Ok(_) => println!("导出成功"),
+                    Err(e) => println!("导出失败: {:?}", e),
+                }
+            }
+            "mg" => {
+                let returned_path = prompt_user_input(returned_prompt);
+                match merge::merge(&cfg_json, &excel_path, &returned_path) {
+                    Ok(_) => println!("合并完成"),
+                    Err(e) => println!("合并失败: {:?}", e),
+                }
+            }
+            "cv" => {
+                let source_path = prompt_user_input(source_prompt);
+                let out_dir = prompt_user_input(report_prompt);
+                if let Err(e) = coverage::report(&cfg_json, &source_path, &out_dir) {
+                    println!("统计失败: {:?}", e);
+                }
+            }
+            "vd" => match validate::validate(&cfg_json, &excel_path) {
+                Ok(_) => println!("检查完成"),
+                Err(e) => println!("检查失败: {:?}", e),
+            },
             "i" => {
                 println!(
                     "当前配置\njson:{} \nexcel:{} \nxml dir:{}",
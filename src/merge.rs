@@ -0,0 +1,330 @@
+use std::{collections::HashMap, error::Error, path::Path};
+
+use crate::{
+    config::ParsedCfg,
+    handoff::{self, BaseSnapshot},
+    read_excel::{self, ExcelSource},
+    source::{self, SourceRow, TranslationSource},
+    write_xlsx,
+};
+
+/// 表格中的一行，row为行号，values为 语言 - 值
+struct MergeRow {
+    row: u32,
+    values: HashMap<String, String>,
+}
+
+/// 合并冲突
+#[derive(Debug, PartialEq)]
+pub struct MergeConflict {
+    pub tag: String,
+    pub lang: String,
+    pub master_value: String,   // 主表中的值
+    pub returned_value: String, // 返回表格中的值
+    pub reason: &'static str,
+}
+
+/// 合并结果
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    pub updated: Vec<(String, String)>, // 已更新的 标签 - 语言
+    pub conflicts: Vec<MergeConflict>,  // 冲突，未写入
+    pub unknown_tags: Vec<String>,      // 主表中不存在的标签
+}
+
+/**
+ * 将翻译人员返回的表格按标签合并到主表中
+ * 两个表格分别按配置解析标签列和语言列，与导出时的快照三方比较，只写入返回的表格中有修改的单元格
+ * 以下情况视为冲突，不写入：
+ * 主表默认语言的文本与导出时不一致，说明导出后原文有修改；
+ * 主表和返回的表格都修改了同一个单元格，且结果不同
+ * 没有快照时，返回表格的默认语言视为导出时的原文，主表中已有的不同翻译视为冲突
+ * @param cfg_json 配置JSON
+ * @param master_path 主表路径，只支持xlsx
+ * @param returned_path 返回的表格路径，xlsx或csv，导出时的快照为 <返回的表格路径>.base.json
+ * @return 合并结果
+ */
+pub fn merge(
+    cfg_json: &str,
+    master_path: &str,
+    returned_path: &str,
+) -> Result<MergeReport, Box<dyn Error>> {
+    let is_xlsx = Path::new(master_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"));
+    if !is_xlsx {
+        return Err(format!("主表只支持xlsx: {}", master_path).into());
+    }
+    let master_cfg = read_excel::parse_cfg_with_excel(master_path, cfg_json)?;
+    let returned_cfg = read_excel::parse_cfg_with_excel(returned_path, cfg_json)?;
+    // 主表读取原始单元格，不做筛选、合并单元格填充等处理，保证写入的位置和比较的值与单元格一致
+    let master_source =
+        ExcelSource::open(master_path, &master_cfg.sheet_name, &master_cfg.date_format)?;
+    let master_rows = read_merge_rows(
+        &master_cfg,
+        source::check_duplicates(Box::new(master_source), &master_cfg).as_mut(),
+    )?;
+    let returned_rows = read_merge_rows(
+        &returned_cfg,
+        source::open_source(returned_path, &returned_cfg)?.as_mut(),
+    )?;
+    let base = match handoff::read_snapshot(returned_path)? {
+        Some(base) => base,
+        None => {
+            println!(
+                "未找到导出时的快照: {}，主表中已有的不同翻译视为冲突",
+                handoff::snapshot_path(returned_path)
+            );
+            BaseSnapshot::new()
+        }
+    };
+
+    let report = compare_rows(
+        &master_cfg,
+        &returned_cfg,
+        &master_rows,
+        &returned_rows,
+        &base,
+    );
+
+    // 计算需要写入的单元格
+    let master_lang_indices: HashMap<&String, u32> = master_cfg
+        .lang_index_map
+        .iter()
+        .map(|(lang, index)| (lang, *index))
+        .collect();
+    let updates: HashMap<(u32, u32), String> = report
+        .updated
+        .iter()
+        .map(|(tag, lang)| {
+            let row = master_rows[tag].row;
+            let value = returned_rows[tag].values[lang].clone();
+            ((row, master_lang_indices[lang]), value)
+        })
+        .collect();
+    if !updates.is_empty() {
+        write_xlsx::update_xlsx_cells(master_path, &master_cfg.sheet_name, &updates)?;
+    }
+    print_report(&report);
+    Ok(report)
+}
+
+/// 读取表格中的行，标签 - 行
+fn read_merge_rows(
+    parsed_cfg: &ParsedCfg,
+    source: &mut dyn TranslationSource,
+) -> Result<HashMap<String, MergeRow>, Box<dyn Error>> {
+    let columns: Vec<u32> = parsed_cfg
+        .lang_index_map
+        .iter()
+        .map(|(_, index)| *index)
+        .collect();
+    let mut rows = HashMap::new();
+    source.read_rows(parsed_cfg.tag_index, &columns, &mut |row: SourceRow| {
+        let values = parsed_cfg
+            .lang_index_map
+            .iter()
+            .map(|(lang, index)| {
+                let value = row.cells.get(index).cloned().unwrap_or_default();
+                (lang.clone(), value)
+            })
+            .collect();
+        rows.insert(
+            row.tag,
+            MergeRow {
+                row: row.row,
+                values,
+            },
+        );
+    })?;
+    Ok(rows)
+}
+
+/// 与导出时的快照比较两个表格，返回需要更新的单元格和冲突，标签按字母顺序
+fn compare_rows(
+    master_cfg: &ParsedCfg,
+    returned_cfg: &ParsedCfg,
+    master_rows: &HashMap<String, MergeRow>,
+    returned_rows: &HashMap<String, MergeRow>,
+    base: &BaseSnapshot,
+) -> MergeReport {
+    let default_lang = &master_cfg.default_lang;
+    let mut report = MergeReport::default();
+    let mut tags: Vec<&String> = returned_rows.keys().collect();
+    tags.sort();
+
+    for tag in tags {
+        let returned = &returned_rows[tag].values;
+        let master = match master_rows.get(tag) {
+            Some(row) => &row.values,
+            None => {
+                report.unknown_tags.push(tag.clone());
+                continue;
+            }
+        };
+
+        let base_values = base.get(tag);
+        // 原文有变化时，整行的翻译都可能已经过时，没有快照时与返回表格中的原文比较
+        let base_source = base_values
+            .and_then(|values| values.get(default_lang))
+            .or(returned.get(default_lang));
+        if let (Some(master_source), Some(base_source)) = (master.get(default_lang), base_source) {
+            if master_source.trim() != base_source.trim() {
+                report.conflicts.push(MergeConflict {
+                    tag: tag.clone(),
+                    lang: default_lang.clone(),
+                    master_value: master_source.clone(),
+                    returned_value: base_source.clone(),
+                    reason: "原文已修改",
+                });
+                continue;
+            }
+        }
+
+        for (lang, _) in &returned_cfg.lang_index_map {
+            if lang == default_lang {
+                continue;
+            }
+            let returned_value = &returned[lang];
+            let master_value = match master.get(lang) {
+                Some(value) => value,
+                None => continue, // 主表中没有这个语言列
+            };
+            if returned_value.trim().is_empty() || returned_value == master_value {
+                continue;
+            }
+            let (master_changed, reason) = match base_values.and_then(|values| values.get(lang)) {
+                // 翻译人员没有修改
+                Some(base_value) if base_value == returned_value => continue,
+                Some(base_value) => (master_value != base_value, "导出后主表已修改"),
+                None => (!master_value.trim().is_empty(), "主表已有不同的翻译"),
+            };
+            if master_changed {
+                report.conflicts.push(MergeConflict {
+                    tag: tag.clone(),
+                    lang: lang.clone(),
+                    master_value: master_value.clone(),
+                    returned_value: returned_value.clone(),
+                    reason,
+                });
+            } else {
+                report.updated.push((tag.clone(), lang.clone()));
+            }
+        }
+    }
+    report
+}
+
+/// 输出合并结果
+fn print_report(report: &MergeReport) {
+    println!("已更新 {} 个单元格", report.updated.len());
+    for tag in &report.unknown_tags {
+        println!("主表中不存在的标签: {}", tag);
+    }
+    if !report.conflicts.is_empty() {
+        println!("冲突 {} 处，未写入:", report.conflicts.len());
+    }
+    for conflict in &report.conflicts {
+        println!(
+            "  [{}] {} {}: 主表 {:?} / 返回 {:?}",
+            conflict.reason,
+            conflict.tag,
+            conflict.lang,
+            conflict.master_value,
+            conflict.returned_value
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_compare_rows() {
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","fr":"French"},"escapeOnly":{}}"#;
+        let master_csv = "tag,English,French\nfill,Hello,\nsame,Yes,Oui\nedited,No,Non\nfixed,Back,Derrière\nboth_changed,Next,Suivant !\nsource_changed,Save all,\n";
+        let returned_csv = "tag,English,French\nfill,Hello,Bonjour\nsame,Yes,Oui\nedited,No,Pas\nfixed,Back,Retour\nboth_changed,Next,Continuer\nsource_changed,Save,Enregistrer\nunknown,Hi,Salut\n";
+        let (parsed_cfg, mut source) = test_util::csv_source(cfg, master_csv);
+        let master = read_merge_rows(&parsed_cfg, &mut source).unwrap();
+        let (_, mut source) = test_util::csv_source(cfg, returned_csv);
+        let returned = read_merge_rows(&parsed_cfg, &mut source).unwrap();
+        let base: BaseSnapshot = [
+            ("fixed", "Back", "Derrière"),
+            ("both_changed", "Next", "Suivant"),
+            ("source_changed", "Save", ""),
+        ]
+        .iter()
+        .map(|(tag, en, fr)| {
+            let values = [("en", en), ("fr", fr)]
+                .iter()
+                .map(|(lang, value)| (lang.to_string(), value.to_string()))
+                .collect();
+            (tag.to_string(), values)
+        })
+        .collect();
+        let report = compare_rows(&parsed_cfg, &parsed_cfg, &master, &returned, &base);
+        // 主表未修改，翻译人员修正了已有的翻译
+        assert_eq!(
+            report.updated,
+            vec![
+                ("fill".to_string(), "fr".to_string()),
+                ("fixed".to_string(), "fr".to_string())
+            ]
+        );
+        assert_eq!(report.unknown_tags, vec!["unknown"]);
+        let conflicts: Vec<(&str, &str)> = report
+            .conflicts
+            .iter()
+            .map(|c| (c.tag.as_str(), c.reason))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("both_changed", "导出后主表已修改"),
+                ("edited", "主表已有不同的翻译"),
+                ("source_changed", "原文已修改")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_with_row_filter() {
+        let dir = std::env::temp_dir().join("excel_to_xml_test_merge");
+        std::fs::create_dir_all(&dir).unwrap();
+        let master_path = dir.join("master.xlsx").to_string_lossy().to_string();
+        let returned_path = dir.join("returned.csv").to_string_lossy().to_string();
+        let rows: Vec<Vec<String>> = [
+            ["tag", "English", "French", "Platform"],
+            ["ios_title", "Title", "", "iOS"],
+            ["hello", "Hello", "", "Android"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+        write_xlsx::write_xlsx(&master_path, "Sheet1", &rows).unwrap();
+        std::fs::write(
+            &returned_path,
+            "tag,English,French,Platform\nios_title,Title,Titre,Android\nhello,Hello,Bonjour,Android\n",
+        )
+        .unwrap();
+
+        // 主表不应用筛选条件，ios_title也按原始行写入
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","fr":"French"},"escapeOnly":{},"rowFilters":["Platform == Android"]}"#;
+        let report = merge(cfg, &master_path, &returned_path).unwrap();
+        assert!(report.unknown_tags.is_empty());
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.updated.len(), 2);
+
+        let parsed_cfg = read_excel::parse_cfg_with_excel(&master_path, cfg).unwrap();
+        let mut source = ExcelSource::open(&master_path, "", "").unwrap();
+        let merged = read_merge_rows(&parsed_cfg, &mut source).unwrap();
+        assert_eq!(merged["ios_title"].row, 1);
+        assert_eq!(merged["ios_title"].values["fr"], "Titre");
+        assert_eq!(merged["hello"].values["fr"], "Bonjour");
+        assert_eq!(source.header().unwrap()[3], "Platform");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,6 +1,9 @@
 use crate::config::ParsedCfg;
-use calamine::{open_workbook, DataType, Reader, Xlsx};
-use std::collections::HashMap;
+use crate::source::{self, SourceRow, TranslationSource};
+use crate::target::SheetMeta;
+use crate::write_xlsx;
+use calamine::{open_workbook, DataRef, Reader, Xlsx};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
 use std::fs::File;
@@ -28,22 +31,265 @@ impl fmt::Display for ExcelError {
 
 impl Error for ExcelError {}
 
-/// 读取Excel文件并解析
-/// 单行单语言数据结构
-#[derive(Default)]
-struct RowSingleLangData {
-    row: Option<u32>,
-    tag: Option<String>,
-    value: Option<String>,
+/// 语言 - 标签值映射列表
+pub type LangMaps = Vec<(String, HashMap<String, String>)>;
+
+/// Excel工作表数据源
+pub struct ExcelSource {
+    workbook: Xlsx<BufReader<File>>,
+    sheet_name: String,
+    date_format: String,
+    reported_cells: HashSet<(u32, u32)>, // 已报告类型转换的单元格
 }
 
-/// 读取Excel文件并解析
-/// 单行多语言数据结构
-#[derive(Default)]
-struct RowMultiLangData {
-    row: Option<u32>,
+impl ExcelSource {
+    /// 打开Excel文件，sheet_name为空时使用第一个工作表，日期单元格按date_format转换为文本
+    pub fn open(
+        file_path: &str,
+        sheet_name: &str,
+        date_format: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let workbook = open_excel_workbook(file_path)?;
+        let sheet_name = if sheet_name.is_empty() {
+            workbook
+                .sheet_names()
+                .first()
+                .cloned()
+                .ok_or_else(|| Box::new(ExcelError::NoSheetsFound) as Box<dyn Error>)?
+        } else {
+            sheet_name.to_string()
+        };
+        Ok(ExcelSource {
+            workbook,
+            sheet_name,
+            date_format: date_format.to_string(),
+            reported_cells: HashSet::new(),
+        })
+    }
+}
+
+impl TranslationSource for ExcelSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        // 获取cell_reader迭代器
+        let cell_reader = self.workbook.worksheet_cells_reader(&self.sheet_name);
+        // 检查是否成功获取cell_reader
+        let mut cell_reader = match cell_reader {
+            Ok(reader) => reader,
+            Err(_) => {
+                return Err(Box::new(ExcelError::NoSheetsFound));
+            }
+        };
+
+        let mut first_row: Vec<String> = Vec::new();
+        // 逐个单元格处理，直到找到第一行的所有单元格
+        while let Some(cell) = cell_reader.next_cell()? {
+            if cell.get_position().0 > 0 {
+                // 已经读取完第一行
+                break;
+            }
+            // 表头按列序号放置，中间的空单元格用空字符串填充
+            let (row, col) = cell.get_position();
+            let value = match cell_text(cell.get_value(), &self.date_format) {
+                (_, Some(CellKind::Error)) => {
+                    return Err(Box::new(ExcelError::CellConversionFailed(format!(
+                        "表头单元格 {}{} 为错误值",
+                        write_xlsx::column_name(col),
+                        row + 1
+                    ))))
+                }
+                (value, _) => value,
+            };
+            if first_row.len() <= col as usize {
+                first_row.resize(col as usize + 1, String::new());
+            }
+            first_row[col as usize] = value;
+        }
+        Ok(first_row)
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let mut cell_reader = self.workbook.worksheet_cells_reader(&self.sheet_name)?;
+        let mut cur_row: Option<u32> = None;
+        let mut cur_tag: Option<String> = None;
+        let mut cur_cells: HashMap<u32, String> = HashMap::new();
+        // 类型转换 - 单元格，只记录尚未报告的单元格
+        let mut coercions: BTreeMap<CellKind, Vec<(u32, u32)>> = BTreeMap::new();
+
+        while let Some(cell) = cell_reader.next_cell()? {
+            let (row, col) = cell.get_position();
+
+            // 换行时处理上一行数据
+            if let Some(prev_row) = cur_row {
+                if row != prev_row {
+                    emit_row(prev_row, cur_tag.take(), &mut cur_cells, visit);
+                }
+            }
+
+            cur_row = Some(row);
+            if row == 0 || (col != tag_index && !columns.contains(&col)) {
+                // 跳过表头行和非相关列
+                continue;
+            }
+
+            let (value, kind) = cell_text(cell.get_value(), &self.date_format);
+            if let Some(kind) = kind {
+                if self.reported_cells.insert((row, col)) {
+                    coercions.entry(kind).or_default().push((row, col));
+                }
+            }
+            if col == tag_index {
+                cur_tag = Some(value.clone());
+            }
+            if columns.contains(&col) {
+                cur_cells.insert(col, value);
+            }
+        }
+
+        // 处理最后一行数据
+        if let Some(row) = cur_row {
+            emit_row(row, cur_tag.take(), &mut cur_cells, visit);
+        }
+        report_coercions(&coercions);
+        Ok(())
+    }
+
+    fn cell_values(
+        &mut self,
+        cells: &HashSet<(u32, u32)>,
+    ) -> Result<HashMap<(u32, u32), String>, Box<dyn Error>> {
+        let mut values = HashMap::new();
+        if cells.is_empty() {
+            return Ok(values);
+        }
+        let mut cell_reader = self.workbook.worksheet_cells_reader(&self.sheet_name)?;
+        while let Some(cell) = cell_reader.next_cell()? {
+            let position = cell.get_position();
+            if cells.contains(&position) {
+                values.insert(position, cell_text(cell.get_value(), &self.date_format).0);
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// 非文本单元格的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CellKind {
+    Number,
+    Bool,
+    Date,
+    Duration,
+    Error,
+}
+
+impl CellKind {
+    fn describe(&self) -> &'static str {
+        match self {
+            CellKind::Number => "数字",
+            CellKind::Bool => "布尔值",
+            CellKind::Date => "日期",
+            CellKind::Duration => "时长",
+            CellKind::Error => "错误值，按空白处理",
+        }
+    }
+}
+
+/**
+ * 将单元格转换为文本，公式单元格使用缓存的计算结果
+ * 整数不带小数部分，布尔值为 TRUE/FALSE，日期按date_format格式化，错误值为空
+ * @param value 单元格的值
+ * @param date_format 日期格式，支持 %Y %m %d %H %M %S
+ * @return 文本，以及非文本单元格的类型
+ */
+fn cell_text(value: &DataRef, date_format: &str) -> (String, Option<CellKind>) {
+    match value {
+        DataRef::String(v) => (v.clone(), None),
+        DataRef::SharedString(v) => (v.to_string(), None),
+        DataRef::Empty => (String::new(), None),
+        DataRef::Int(v) => (v.to_string(), Some(CellKind::Number)),
+        DataRef::Float(v) => (format_number(*v), Some(CellKind::Number)),
+        DataRef::Bool(v) => (
+            if *v { "TRUE" } else { "FALSE" }.to_string(),
+            Some(CellKind::Bool),
+        ),
+        DataRef::DateTime(v) if v.is_duration() => {
+            let seconds = (v.as_f64() * 86400.0).round() as i64;
+            let text = format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            );
+            (text, Some(CellKind::Duration))
+        }
+        DataRef::DateTime(v) => {
+            let (year, month, day, hour, minute, second, _) = v.to_ymd_hms_milli();
+            let text = date_format
+                .replace("%Y", &format!("{:04}", year))
+                .replace("%m", &format!("{:02}", month))
+                .replace("%d", &format!("{:02}", day))
+                .replace("%H", &format!("{:02}", hour))
+                .replace("%M", &format!("{:02}", minute))
+                .replace("%S", &format!("{:02}", second));
+            (text, Some(CellKind::Date))
+        }
+        DataRef::DateTimeIso(v) => (v.clone(), Some(CellKind::Date)),
+        DataRef::DurationIso(v) => (v.clone(), Some(CellKind::Duration)),
+        DataRef::Error(_) => (String::new(), Some(CellKind::Error)),
+    }
+}
+
+/// 与Excel一样保留15位有效数字，0.1+0.2 输出 0.3 而不是 0.30000000000000004
+/// f64的Display不会输出多余的 .0
+fn format_number(value: f64) -> String {
+    let rounded: f64 = format!("{:.14e}", value).parse().unwrap_or(value);
+    rounded.to_string()
+}
+
+/// 输出类型转换的单元格，每种类型最多列出5个位置
+fn report_coercions(coercions: &BTreeMap<CellKind, Vec<(u32, u32)>>) {
+    for (kind, cells) in coercions {
+        let mut locations: Vec<String> = cells
+            .iter()
+            .take(5)
+            .map(|(row, col)| format!("{}{}", write_xlsx::column_name(*col), row + 1))
+            .collect();
+        if cells.len() > 5 {
+            locations.push("...".to_string());
+        }
+        println!(
+            "[单元格] {}个{}: {}",
+            cells.len(),
+            kind.describe(),
+            locations.join(", ")
+        );
+    }
+}
+
+/// 标签不为空时输出当前行，并清空单元格
+fn emit_row(
+    row: u32,
     tag: Option<String>,
-    value: Option<HashMap<u32, String>>,
+    cells: &mut HashMap<u32, String>,
+    visit: &mut dyn FnMut(SourceRow),
+) {
+    let cells = std::mem::take(cells);
+    if let Some(tag) = tag {
+        let tag_trim = tag.trim();
+        if !tag_trim.is_empty() {
+            visit(SourceRow {
+                row,
+                tag: tag_trim.to_string(),
+                cells,
+                meta: None,
+            });
+        }
+    }
 }
 
 /**
@@ -58,48 +304,23 @@ pub fn parse_cfg_with_excel(
 ) -> Result<ParsedCfg, Box<dyn Error>> {
     println!("开始解析Excel文件: {} config_json: {}", file_path, config_json);
     // 解析配置JSON
-    let mut parsed_cfg = ParsedCfg::from_json(config_json)?;
-
-    // 打开Excel文件并获取工作表
-    let mut workbook = open_excel_workbook(file_path)?;
-
-    let sheet_names = workbook.sheet_names();
-    let mut sheet_name = &parsed_cfg.sheet_name;
-    if sheet_name.is_empty() {
-         sheet_name = sheet_names
-        .get(0)
-        .ok_or_else(|| Box::new(ExcelError::NoSheetsFound))?
-    }
-
-    // 获取cell_reader迭代器
-    let cell_reader = workbook.worksheet_cells_reader(&sheet_name);
-    // 检查是否成功获取cell_reader
-    let mut cell_reader = match cell_reader {
-        Ok(reader) => reader,
-        Err(_) => {
-            return Err(Box::new(ExcelError::NoSheetsFound));
-        }
-    };
+    let parsed_cfg = ParsedCfg::from_json(config_json)?;
+    let mut source = source::open_source(file_path, &parsed_cfg)?;
+    parse_cfg_with_source(source.as_mut(), parsed_cfg)
+}
 
+/**
+ * 根据数据源表头解析标签列、语言列等序号
+ * @param source 数据源
+ * @param parsed_cfg 由配置JSON解析的配置
+ * @return 解析后的配置
+ */
+pub fn parse_cfg_with_source(
+    source: &mut dyn TranslationSource,
+    mut parsed_cfg: ParsedCfg,
+) -> Result<ParsedCfg, Box<dyn Error>> {
     // 1. 读取表头行
-    let mut first_row: Vec<String> = Vec::new();
-    // 逐个单元格处理，直到找到第一行的所有单元格
-    while let Some(cell) = cell_reader.next_cell()? {
-        if cell.get_position().0 > 0 {
-            // 已经读取完第一行
-            break;
-        }
-        // 安全地转换单元格值为字符串
-        match cell.get_value().as_string() {
-            Some(value) => first_row.push(value),
-            None => {
-                return Err(Box::new(ExcelError::CellConversionFailed(format!(
-                    "无法将单元格 {:?} 转换为字符串",
-                    cell.get_position()
-                ))))
-            }
-        }
-    }
+    let first_row = source.header()?;
     if first_row.is_empty() {
         println!("工作表为空或没有数据");
         return Err(Box::new(ExcelError::InvalidFirstLine));
@@ -113,6 +334,37 @@ pub fn parse_cfg_with_excel(
     let lang_index_map = find_language_indices(&first_row, &parsed_cfg.lang_map);
     parsed_cfg.tag_index = tag_index;
     parsed_cfg.lang_index_map = lang_index_map;
+
+    // 查找备注列索引，备注列可选
+    if !parsed_cfg.comment_name.is_empty() {
+        parsed_cfg.comment_index = find_tag_index(&first_row, &parsed_cfg.comment_name).ok();
+        if parsed_cfg.comment_index.is_none() {
+            println!("未找到备注列: {}", parsed_cfg.comment_name);
+        }
+    }
+
+    // 查找最大长度列索引，最大长度列可选
+    if !parsed_cfg.max_length_name.is_empty() {
+        parsed_cfg.max_length_index = find_tag_index(&first_row, &parsed_cfg.max_length_name).ok();
+        if parsed_cfg.max_length_index.is_none() {
+            println!("未找到最大长度列: {}", parsed_cfg.max_length_name);
+        }
+    }
+
+    // 查找标记列索引，标记列可选
+    if !parsed_cfg.translatable_name.is_empty() {
+        parsed_cfg.translatable_index =
+            find_tag_index(&first_row, &parsed_cfg.translatable_name).ok();
+        if parsed_cfg.translatable_index.is_none() {
+            println!("未找到标记列: {}", parsed_cfg.translatable_name);
+        }
+    }
+    if !parsed_cfg.formatted_name.is_empty() {
+        parsed_cfg.formatted_index = find_tag_index(&first_row, &parsed_cfg.formatted_name).ok();
+        if parsed_cfg.formatted_index.is_none() {
+            println!("未找到标记列: {}", parsed_cfg.formatted_name);
+        }
+    }
     Ok(parsed_cfg)
 }
 
@@ -122,7 +374,117 @@ pub fn open_excel_workbook(file_path: &str) -> Result<Xlsx<BufReader<File>>, Box
     Ok(workbook)
 }
 
+/// 一次读取所有语种，并按语言拆分为 标签-值 映射
+/// * @param source 数据源
+/// * @param parsed_cfg 解析后的配置
+/// * @return 语言 - 标签值映射，顺序与lang_index_map一致
+pub fn read_lang_maps(
+    source: &mut dyn TranslationSource,
+    parsed_cfg: &ParsedCfg,
+) -> Result<LangMaps, Box<dyn Error>> {
+    let lang_index_vec: Vec<u32> = parsed_cfg
+        .lang_index_map
+        .iter()
+        .map(|(_, index)| *index)
+        .collect();
+
+    let mut tag_value_map: HashMap<String, HashMap<u32, String>> = HashMap::new();
+    process_excel_multi_lang(
+        source,
+        parsed_cfg.tag_index,
+        lang_index_vec,
+        &mut tag_value_map,
+    )?;
+
+    let lang_maps = parsed_cfg
+        .lang_index_map
+        .iter()
+        .map(|(lang, index)| {
+            let map = tag_value_map
+                .iter()
+                .map(|(tag, values)| (tag.clone(), values.get(index).cloned().unwrap_or_default()))
+                .collect();
+            (lang.clone(), map)
+        })
+        .collect();
+    Ok(lang_maps)
+}
+
+/// 读取与语言无关的标签信息，包括行号、备注、最大长度和标记
+pub fn read_sheet_meta(
+    source: &mut dyn TranslationSource,
+    parsed_cfg: &ParsedCfg,
+) -> Result<SheetMeta, Box<dyn Error>> {
+    let mut meta = SheetMeta::default();
+    let columns: Vec<u32> = parsed_cfg
+        .comment_index
+        .into_iter()
+        .chain(parsed_cfg.max_length_index)
+        .chain(parsed_cfg.translatable_index)
+        .chain(parsed_cfg.formatted_index)
+        .collect();
+    source.read_rows(parsed_cfg.tag_index, &columns, &mut |mut row| {
+        if let Some(comment) = parsed_cfg
+            .comment_index
+            .and_then(|index| row.cells.remove(&index))
+        {
+            meta.comment_map.insert(row.tag.clone(), comment);
+        }
+        if let Some(max_length) = parsed_cfg
+            .max_length_index
+            .and_then(|index| row.cells.remove(&index))
+            .filter(|value| !value.trim().is_empty())
+        {
+            // 数字单元格可能读取为 20.0
+            match max_length.trim().parse::<f64>() {
+                Ok(length) if length >= 0.0 => {
+                    meta.max_length_map.insert(row.tag.clone(), length as usize);
+                }
+                _ => println!(
+                    "[长度] {} (第{}行): 最大长度不是数字 {:?}",
+                    row.tag,
+                    row.row + 1,
+                    max_length
+                ),
+            }
+        }
+        let flags = [
+            (parsed_cfg.translatable_index, &mut meta.translatable_map),
+            (parsed_cfg.formatted_index, &mut meta.formatted_map),
+        ];
+        for (index, map) in flags {
+            if let Some(flag) = index
+                .and_then(|index| row.cells.remove(&index))
+                .and_then(|value| parse_flag(&value))
+            {
+                map.insert(row.tag.clone(), flag);
+            }
+        }
+        // 数据源自带的标签信息，配置的列优先
+        if let Some(row_meta) = row.meta.take() {
+            if let Some(comment) = row_meta.comment {
+                meta.comment_map.entry(row.tag.clone()).or_insert(comment);
+            }
+            if let Some(flag) = row_meta.translatable {
+                meta.translatable_map.entry(row.tag.clone()).or_insert(flag);
+            }
+            if let Some(flag) = row_meta.formatted {
+                meta.formatted_map.entry(row.tag.clone()).or_insert(flag);
+            }
+        }
+        meta.row_map.insert(row.tag, row.row);
+    })?;
+    Ok(meta)
+}
 
+/// 解析标记列的值，为空或无法识别时返回None
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "是" => Some(true),
+        "false" | "no" | "n" | "0" | "否" => Some(false),
+        _ => None,
+    }
+}
 
 /// 查找标签索引
 fn find_tag_index(first_row: &[String], tag_name: &str) -> Result<u32, Box<dyn Error>> {
@@ -151,131 +513,71 @@ fn find_language_indices(
 
 /// 一次解析单个语种
 /// 内存占用低，解析全部语言更耗时
-/// * @param workbook Excel工作簿
-/// * @param sheet_name 工作表名称
+/// * @param source 数据源
 /// * @param tag_index 标签列索引
 /// * @param lang_index 语言列索引
 /// * @param tag_value_map 标签值映射
 /// * @return 解析是否成功
 pub fn process_excel_single_lang(
-    workbook: &mut Xlsx<BufReader<File>>,
-    sheet_name: &str,
+    source: &mut dyn TranslationSource,
     tag_index: u32,
     lang_index: u32,
     tag_value_map: &mut HashMap<String, String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut cell_reader = workbook.worksheet_cells_reader(sheet_name)?;
-    let mut cur = RowSingleLangData::default();
-
-    while let Some(cell) = cell_reader.next_cell()? {
-        // 其余代码保持不变
-        let (row, col) = cell.get_position();
-
-        // 换行时处理上一行数据
-        if let Some(prev_row) = cur.row {
-            if row != prev_row {
-                if let (Some(tag), Some(value)) = (&cur.tag, &cur.value) {
-                    let tag_trim = tag.trim();
-                    if !tag_trim.is_empty() {
-                        tag_value_map.insert(tag_trim.to_string(), value.clone());
-                    }
-                }
-                cur = RowSingleLangData::default();
-            }
-        }
-
-        cur.row = Some(row);
-        if row == 0 || (col != tag_index && col != lang_index) {
-            // 跳过表头行和非相关列
-            continue;
-        }
-
-        if col == tag_index {
-            cur.tag = cell.get_value().as_string().map(String::from);
-        } else if col == lang_index {
-            let raw = cell.get_value().as_string().unwrap_or("".to_owned());
-            cur.value = Some(raw);
-        }
-    }
-
-    // 处理最后一行数据
-    if let (Some(tag), Some(value)) = (&cur.tag, &cur.value) {
-        let tag_trim = tag.trim();
-        if !tag_trim.is_empty() {
-            tag_value_map.insert(tag_trim.to_string(), value.clone());
+    source.read_rows(tag_index, &[lang_index], &mut |mut row| {
+        // 没有该语言单元格的行不写入
+        if let Some(value) = row.cells.remove(&lang_index) {
+            tag_value_map.insert(row.tag, value);
         }
-    }
-
-    Ok(())
+    })
 }
 
 /// 一次解析所有语种
 /// * 解析全部语言耗时更少，但内存占用更高
-/// * @param workbook Excel工作簿
-/// * @param sheet_name 工作表名称
+/// * @param source 数据源
 /// * @param tag_index 标签列索引
 /// * @param lang_index_vec 语言列索引向量
 /// * @param tag_value_map 标签值映射
 /// * @return 解析是否成功
 pub fn process_excel_multi_lang(
-    workbook: &mut Xlsx<BufReader<File>>,
-    sheet_name: &str,
+    source: &mut dyn TranslationSource,
     tag_index: u32,
     lang_index_vec: Vec<u32>,
     tag_value_map: &mut HashMap<String, HashMap<u32, String>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut cell_reader = workbook.worksheet_cells_reader(sheet_name)?;
-    let mut cur = RowMultiLangData::default();
-
-    while let Some(cell) = cell_reader.next_cell()? {
-        // 其余代码保持不变
-        let (row, col) = cell.get_position();
-
-        // 换行时处理上一行数据
-        if let Some(prev_row) = cur.row {
-            if row != prev_row {
-                if let (Some(tag), Some(value)) = (&cur.tag, &cur.value) {
-                    let tag_trim = tag.trim();
-                    if !tag_trim.is_empty() {
-                        tag_value_map.insert(tag_trim.to_string(), value.clone());
-                    }
-                }
-                // 重置当前行数据
-                cur = RowMultiLangData::default();
-            }
-        }
-
-        cur.row = Some(row);
-        if row == 0 || (col != tag_index && !lang_index_vec.contains(&col)) {
-            // 跳过表头行和非相关列
-            continue;
+    source.read_rows(tag_index, &lang_index_vec, &mut |row| {
+        // 没有任何语言单元格的行不写入
+        if !row.cells.is_empty() {
+            tag_value_map.insert(row.tag, row.cells);
         }
+    })
+}
 
-        if col == tag_index {
-            // 处理tag
-            cur.tag = cell.get_value().as_string().map(String::from);
-        } else if lang_index_vec.contains(&col) {
-            let raw = cell.get_value().as_string().unwrap_or("".to_owned());
-            match cur.value {
-                Some(ref mut map) => {
-                    map.insert(col, raw);
-                }
-                None => {
-                    let mut map = HashMap::new();
-                    map.insert(col, raw);
-                    cur.value = Some(map);
-                }
-            }
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::{CellErrorType, ExcelDateTime, ExcelDateTimeType};
 
-    // 处理最后一行数据
-    if let (Some(tag), Some(value)) = (&cur.tag, &cur.value) {
-        let tag_trim = tag.trim();
-        if !tag_trim.is_empty() {
-            tag_value_map.insert(tag_trim.to_string(), value.clone());
-        }
+    #[test]
+    fn test_cell_text() {
+        let text = |value: DataRef| cell_text(&value, "%Y/%m/%d %H:%M");
+        assert_eq!(
+            text(DataRef::Float(404.0)),
+            ("404".into(), Some(CellKind::Number))
+        );
+        assert_eq!(text(DataRef::Float(1.5)).0, "1.5");
+        assert_eq!(text(DataRef::Float(0.1 + 0.2)).0, "0.3");
+        assert_eq!(text(DataRef::Float(1234567.891)).0, "1234567.891");
+        assert_eq!(text(DataRef::Int(2024)).0, "2024");
+        assert_eq!(text(DataRef::Bool(false)).0, "FALSE");
+        assert_eq!(text(DataRef::SharedString("Hi")), ("Hi".into(), None));
+        let date = ExcelDateTime::new(45000.5, ExcelDateTimeType::DateTime, false);
+        assert_eq!(text(DataRef::DateTime(date)).0, "2023/03/15 12:00");
+        let duration = ExcelDateTime::new(1.5, ExcelDateTimeType::TimeDelta, false);
+        assert_eq!(text(DataRef::DateTime(duration)).0, "36:00:00");
+        assert_eq!(
+            text(DataRef::Error(CellErrorType::NA)),
+            (String::new(), Some(CellKind::Error))
+        );
     }
-
-    Ok(())
 }
@@ -0,0 +1,183 @@
+use std::{collections::HashMap, error::Error};
+
+use crate::{target, write_po::PoTarget};
+
+/// PO条目中当前正在读取的字段
+#[derive(PartialEq)]
+enum PoField {
+    None,
+    Ctxt,
+    Id,
+    Str,
+    Ignored,
+}
+
+/// 单个PO条目
+#[derive(Default)]
+struct PoEntry {
+    ctxt: Option<String>,
+    id: Option<String>,
+    msgstr: Option<String>,
+    fuzzy: bool,
+}
+
+/**
+ * 使用PO文件作为数据源更新配置的输出目标
+ * 每种语言读取 po_dir 下对应的 <locale>.po，缺失的语言跳过
+ * @param cfg_json 配置JSON
+ * @param po_dir PO文件所在文件夹
+ * @param xml_dir_path XML所在模块路径
+ */
+pub fn update(cfg_json: &str, po_dir: &str, xml_dir_path: &str) -> Result<(), Box<dyn Error>> {
+    target::update_from_target(&PoTarget, cfg_json, po_dir, xml_dir_path)
+}
+
+/// 解析PO文件内容，返回 标签 - 译文 映射
+/// 有msgctxt时使用msgctxt作为标签，否则使用msgid；标记为fuzzy的条目视为未翻译
+pub fn parse_po(content: &str) -> HashMap<String, String> {
+    let mut tag_value_map = HashMap::new();
+    let mut entry = PoEntry::default();
+    let mut field = PoField::None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            flush_entry(&mut entry, &mut tag_value_map);
+            field = PoField::None;
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if entry.msgstr.is_some() {
+                flush_entry(&mut entry, &mut tag_value_map);
+            }
+            if comment.starts_with(',') && comment.contains("fuzzy") {
+                entry.fuzzy = true;
+            }
+            field = PoField::None;
+            continue;
+        }
+
+        if line.starts_with('"') {
+            // 多行字符串的后续行
+            let text = unquote_po(line);
+            match field {
+                PoField::Ctxt => append(&mut entry.ctxt, &text),
+                PoField::Id => append(&mut entry.id, &text),
+                PoField::Str => append(&mut entry.msgstr, &text),
+                PoField::None | PoField::Ignored => {}
+            }
+            continue;
+        }
+
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if matches!(keyword, "msgctxt" | "msgid") && entry.msgstr.is_some() {
+            flush_entry(&mut entry, &mut tag_value_map);
+        }
+        let text = unquote_po(rest.trim());
+        field = match keyword {
+            "msgctxt" => {
+                entry.ctxt = Some(text);
+                PoField::Ctxt
+            }
+            "msgid" => {
+                entry.id = Some(text);
+                PoField::Id
+            }
+            "msgstr" | "msgstr[0]" => {
+                entry.msgstr = Some(text);
+                PoField::Str
+            }
+            _ => PoField::Ignored, // msgid_plural、msgstr[n] 等
+        };
+    }
+    flush_entry(&mut entry, &mut tag_value_map);
+    tag_value_map
+}
+
+/// 保存当前条目并重置
+fn flush_entry(entry: &mut PoEntry, tag_value_map: &mut HashMap<String, String>) {
+    let cur = std::mem::take(entry);
+    let tag = match (cur.ctxt, cur.id) {
+        (Some(ctxt), _) => ctxt,
+        (None, Some(id)) => id,
+        (None, None) => return,
+    };
+    if tag.is_empty() {
+        // 文件头
+        return;
+    }
+    let value = if cur.fuzzy {
+        String::new()
+    } else {
+        cur.msgstr.unwrap_or_default()
+    };
+    tag_value_map.insert(tag, value);
+}
+
+fn append(target: &mut Option<String>, text: &str) {
+    target.get_or_insert_with(String::new).push_str(text);
+}
+
+/// 去掉两端引号并还原转义字符
+fn unquote_po(value: &str) -> String {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_po() {
+        let content = r#"msgid ""
+msgstr ""
+"Language: zh_TW\n"
+
+msgctxt "app_name"
+msgid "App"
+msgstr "應用"
+
+#, fuzzy
+msgctxt "fuzzy_tag"
+msgid "Fuzzy"
+msgstr "模糊"
+
+msgctxt "multi_line"
+msgid ""
+"Line 1\n"
+"Line \"2\""
+msgstr ""
+"第一行\n"
+"第二行"
+
+msgid "no_ctxt"
+msgstr "無上下文"
+"#;
+        let map = parse_po(content);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map["app_name"], "應用");
+        assert_eq!(map["fuzzy_tag"], "");
+        assert_eq!(map["multi_line"], "第一行\n第二行");
+        assert_eq!(map["no_ctxt"], "無上下文");
+    }
+}
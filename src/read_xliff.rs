@@ -0,0 +1,227 @@
+use std::{collections::HashMap, error::Error};
+
+use crate::{target, write_xliff::XliffTarget};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+const XLIFF_B_TRANS_UNIT: &[u8] = b"trans-unit";
+const XLIFF_B_UNIT: &[u8] = b"unit";
+const XLIFF_B_SEGMENT: &[u8] = b"segment";
+const XLIFF_B_IGNORABLE: &[u8] = b"ignorable";
+const XLIFF_B_SOURCE: &[u8] = b"source";
+const XLIFF_B_TARGET: &[u8] = b"target";
+const XLIFF_B_ORIGINAL_DATA: &[u8] = b"originalData";
+const XLIFF_B_DATA: &[u8] = b"data";
+const XLIFF_B_ID: &[u8] = b"id";
+const XLIFF_B_RESNAME: &[u8] = b"resname";
+const XLIFF_B_NAME: &[u8] = b"name";
+const XLIFF_B_DATA_REF: &[u8] = b"dataRef";
+
+/**
+ * 使用翻译后的XLIFF文件更新配置的输出目标
+ * 每种语言读取 xliff_dir 下对应的 <language-tag>.xlf，缺失的语言跳过
+ * 默认语言使用XLIFF文件中的source
+ * @param cfg_json 配置JSON
+ * @param xliff_dir XLIFF文件所在文件夹
+ * @param xml_dir_path XML所在模块路径
+ */
+pub fn update(cfg_json: &str, xliff_dir: &str, xml_dir_path: &str) -> Result<(), Box<dyn Error>> {
+    target::update_from_target(&XliffTarget, cfg_json, xliff_dir, xml_dir_path)
+}
+
+/// 解析XLIFF 1.2/2.0内容，返回 标签 - 译文 映射
+/// 没有target的翻译单元视为未翻译，不会出现在结果中
+pub fn parse_xliff(content: &str) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    parse_units(content, XLIFF_B_TARGET)
+}
+
+/// 解析XLIFF 1.2/2.0内容，返回 标签 - 原文 映射
+pub fn parse_xliff_source(content: &str) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    parse_units(content, XLIFF_B_SOURCE)
+}
+
+/**
+ * 读取每个翻译单元中的source或target
+ * 只读取trans-unit(1.2)或segment(2.0)的直接子元素，alt-trans中的候选译文会被忽略
+ * 空的行内标签通过dataRef还原为originalData中的原始内容，无法还原时跳过该翻译单元
+ * @param content XLIFF内容
+ * @param element 读取的元素名称
+ */
+fn parse_units(content: &str, element: &[u8]) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let mut xml_reader = Reader::from_str(content);
+    let mut tag_value_map = HashMap::new();
+
+    // 当前元素的所有上级元素
+    let mut parents: Vec<Vec<u8>> = Vec::new();
+    let mut current_id: Option<String> = None;
+    let mut current_value: Option<String> = None;
+    let mut value_depth = 0;
+    // 2.0 originalData中的 id - 原始内容
+    let mut original_data: HashMap<String, String> = HashMap::new();
+    let mut current_data: Option<(String, String)> = None;
+    // 当前翻译单元中无法还原的行内标签
+    let mut unresolved: Vec<String> = Vec::new();
+
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) => {
+                let name = e.local_name().as_ref().to_vec();
+                let parent = parents.last().map(|p| p.as_slice());
+                if value_depth > 0 {
+                    // 行内标签，只保留文本
+                    value_depth += 1;
+                } else if name == XLIFF_B_TRANS_UNIT || name == XLIFF_B_UNIT {
+                    current_id = unit_id(&e);
+                    current_value = None;
+                    original_data.clear();
+                    unresolved.clear();
+                } else if name == element
+                    && current_id.is_some()
+                    && matches!(
+                        parent,
+                        Some(XLIFF_B_TRANS_UNIT | XLIFF_B_SEGMENT | XLIFF_B_IGNORABLE)
+                    )
+                {
+                    value_depth = 1;
+                    // 2.0中一个unit可能包含多个segment，依次拼接
+                    current_value.get_or_insert_with(String::new);
+                } else if name == XLIFF_B_DATA && parent == Some(XLIFF_B_ORIGINAL_DATA) {
+                    if let Some(id) = attr_value(&e, XLIFF_B_ID) {
+                        current_data = Some((id, String::new()));
+                    }
+                }
+                parents.push(name);
+            }
+            Event::Empty(e) if value_depth > 0 => {
+                let data = attr_value(&e, XLIFF_B_DATA_REF).and_then(|id| original_data.get(&id));
+                match (data, current_value.as_mut()) {
+                    (Some(data), Some(value)) => value.push_str(data),
+                    _ => unresolved.push(format!(
+                        "<{}/>",
+                        String::from_utf8_lossy(e.local_name().as_ref())
+                    )),
+                }
+            }
+            Event::End(e) => {
+                parents.pop();
+                let name = e.local_name();
+                if value_depth > 0 {
+                    value_depth -= 1;
+                } else if name.as_ref() == XLIFF_B_DATA {
+                    if let Some((id, data)) = current_data.take() {
+                        original_data.insert(id, data);
+                    }
+                } else if name.as_ref() == XLIFF_B_TRANS_UNIT || name.as_ref() == XLIFF_B_UNIT {
+                    if let (Some(id), Some(value)) = (current_id.take(), current_value.take()) {
+                        if unresolved.is_empty() {
+                            tag_value_map.insert(id, value);
+                        } else {
+                            println!(
+                                "[XLIFF] {}: 无法还原的行内标签 {}，已跳过",
+                                id,
+                                unresolved.join(" ")
+                            );
+                        }
+                    }
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape()?;
+                if value_depth > 0 {
+                    if let Some(value) = current_value.as_mut() {
+                        value.push_str(&text);
+                    }
+                } else if let Some((_, data)) = current_data.as_mut() {
+                    data.push_str(&text);
+                }
+            }
+            Event::CData(e) => {
+                let text = String::from_utf8_lossy(&e);
+                if value_depth > 0 {
+                    if let Some(value) = current_value.as_mut() {
+                        value.push_str(&text);
+                    }
+                } else if let Some((_, data)) = current_data.as_mut() {
+                    data.push_str(&text);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(tag_value_map)
+}
+
+/// 获取属性值
+fn attr_value(e: &BytesStart, key: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == key)
+        .and_then(|attr| attr.unescape_value().ok().map(|v| v.to_string()))
+}
+
+/// 获取翻译单元的标签，优先使用resname/name，其次使用id
+fn unit_id(e: &BytesStart) -> Option<String> {
+    let mut id = None;
+    for attr in e.attributes().flatten() {
+        let key = attr.key.as_ref();
+        if key == XLIFF_B_RESNAME || key == XLIFF_B_NAME {
+            return attr.unescape_value().ok().map(|v| v.to_string());
+        }
+        if key == XLIFF_B_ID {
+            id = attr.unescape_value().ok().map(|v| v.to_string());
+        }
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xliff() {
+        let xliff_1_2 = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file original="strings.xml" source-language="en" target-language="zh-TW" datatype="plaintext">
+    <body>
+      <trans-unit id="1" resname="app_name">
+        <source>App</source>
+        <target>應用 &amp; <g id="b">工具</g></target>
+        <alt-trans origin="mt"><target>机器翻译</target></alt-trans>
+      </trans-unit>
+      <trans-unit id="2" resname="count">
+        <source>%d items</source>
+        <target><x id="1"/> 項</target>
+      </trans-unit>
+      <trans-unit id="untranslated">
+        <source>Hello</source>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>"#;
+        let map = parse_xliff(xliff_1_2).unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["app_name"], "應用 & 工具");
+        assert_eq!(parse_xliff_source(xliff_1_2).unwrap()["count"], "%d items");
+
+        let xliff_2_0 = r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="ja">
+  <file id="strings">
+    <unit id="hello">
+      <notes><note>greeting</note></notes>
+      <segment><source>Hello </source><target>こんにちは</target></segment>
+      <segment><source>world</source><target>世界</target></segment>
+    </unit>
+    <unit id="count">
+      <originalData><data id="d1">%d</data></originalData>
+      <segment><source><ph id="1" dataRef="d1"/> items</source><target><ph id="1" dataRef="d1"/> 個</target></segment>
+    </unit>
+  </file>
+</xliff>"#;
+        let map = parse_xliff(xliff_2_0).unwrap();
+        assert_eq!(map["hello"], "こんにちは世界");
+        assert_eq!(map["count"], "%d 個");
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use crate::{
+    config::ParsedCfg,
+    source::{MemorySource, RowMeta, SourceRow, TranslationSource},
+    target::{Entries, LocalizationTarget},
+    write_xlsx,
+    write_xml::{self, AndroidTarget},
+};
+
+/// strings.xml数据源，用于把另一个项目的strings.xml作为表格写入
+/// 表头为 tagName 和 langMap 中的语言名称，备注和标记来自默认语言文件
+pub struct XmlSource {
+    rows: MemorySource,
+    meta_map: HashMap<String, RowMeta>, // 标签 - 标签信息
+}
+
+impl XmlSource {
+    /**
+     * 读取各语言的strings.xml
+     * @param xml_dir_path XML所在模块路径
+     * @param parsed_cfg 配置，使用其中的 tagName、langMap 和 defaultLang
+     */
+    pub fn open(xml_dir_path: &str, parsed_cfg: &ParsedCfg) -> Result<Self, Box<dyn Error>> {
+        let files = AndroidTarget.discover(xml_dir_path, parsed_cfg)?;
+        let meta_map = match files
+            .iter()
+            .find(|(lang, _)| *lang == parsed_cfg.default_lang)
+        {
+            Some((_, path)) => write_xml::read_string_meta(path)?,
+            None => HashMap::new(),
+        };
+        Ok(XmlSource {
+            rows: MemorySource::new(build_rows(parsed_cfg, &files)?),
+            meta_map,
+        })
+    }
+}
+
+impl TranslationSource for XmlSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.rows.header()
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let meta_map = &self.meta_map;
+        self.rows.read_rows(tag_index, columns, &mut |mut row| {
+            row.meta = meta_map.get(&row.tag).cloned();
+            visit(row);
+        })
+    }
+}
+
+/**
+ * 从已有的strings.xml生成Excel表格，用于接入还没有表格的项目
+ * 表头为 tagName 和 langMap 中的语言名称，生成的表格可直接用于 update
+ * @param cfg_json 配置JSON
+ * @param xml_dir_path XML所在模块路径
+ * @param excel_path 输出的Excel文件路径
+ */
+pub fn export_excel(
+    cfg_json: &str,
+    xml_dir_path: &str,
+    excel_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = ParsedCfg::from_json(cfg_json)?;
+    let rows = read_xml_rows(&parsed_cfg, xml_dir_path)?;
+    let sheet_name = if parsed_cfg.sheet_name.is_empty() {
+        "Sheet1"
+    } else {
+        parsed_cfg.sheet_name.as_str()
+    };
+    write_xlsx::write_xlsx(excel_path, sheet_name, &rows)?;
+    println!("写入Excel文件: {}, 共{}行", excel_path, rows.len() - 1);
+    Ok(())
+}
+
+/**
+ * 读取各语言的strings.xml，组合为表格，第一行为表头
+ * 标签顺序以默认语言文件为准，其他语言中多出的标签追加在后面
+ * @param parsed_cfg 解析后的配置
+ * @param xml_dir_path XML所在模块路径
+ * @return 表格数据
+ */
+pub fn read_xml_rows(
+    parsed_cfg: &ParsedCfg,
+    xml_dir_path: &str,
+) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let files = AndroidTarget.discover(xml_dir_path, parsed_cfg)?;
+    build_rows(parsed_cfg, &files)
+}
+
+/// 按 语言 - 文件路径 读取strings.xml，组合为表格
+fn build_rows(parsed_cfg: &ParsedCfg, files: &Entries) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let target = AndroidTarget;
+    let files: HashMap<&String, &String> = files.iter().map(|(lang, path)| (lang, path)).collect();
+
+    // 默认语言排在最前面，以便确定标签顺序
+    let mut langs: Vec<&String> = parsed_cfg.lang_map.iter().map(|(lang, _)| lang).collect();
+    langs.sort_by_key(|lang| **lang != parsed_cfg.default_lang);
+
+    let mut tags: Vec<String> = Vec::new();
+    let mut seen_tags = HashSet::new();
+    let mut lang_values: HashMap<&String, HashMap<String, String>> = HashMap::new();
+    for lang in langs {
+        let path = match files.get(lang) {
+            Some(path) => path,
+            None => {
+                println!("未找到 {} 的strings.xml", lang);
+                continue;
+            }
+        };
+        let entries = target.read_entries(path)?;
+        for (tag, _) in &entries {
+            if seen_tags.insert(tag.clone()) {
+                tags.push(tag.clone());
+            }
+        }
+        lang_values.insert(lang, entries.into_iter().collect());
+    }
+
+    let mut header = vec![parsed_cfg.tag_name.clone()];
+    header.extend(parsed_cfg.lang_map.iter().map(|(_, name)| name.clone()));
+    let mut rows = vec![header];
+    for tag in tags {
+        let mut row = vec![tag.clone()];
+        row.extend(parsed_cfg.lang_map.iter().map(|(lang, _)| {
+            lang_values
+                .get(lang)
+                .and_then(|values| values.get(&tag))
+                .cloned()
+                .unwrap_or_default()
+        }));
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_excel, source};
+    use std::fs;
+
+    #[test]
+    fn test_xml_source() {
+        let dir = std::env::temp_dir().join("excel_to_xml_test_xml_source");
+        fs::remove_dir_all(&dir).ok();
+        let res = dir.join("app/src/main/res");
+        fs::create_dir_all(res.join("values")).unwrap();
+        fs::create_dir_all(res.join("values-zh")).unwrap();
+        fs::write(
+            res.join("values/strings.xml"),
+            r#"<resources>
+    <!-- 首页标题 -->
+    <string name="title">Home</string>
+    <string name="app_id" translatable="false">com.example</string>
+
+    <!-- 与下一个string之间有空行，不是备注 -->
+
+    <string name="count" formatted="false">%d%%</string>
+</resources>"#,
+        )
+        .unwrap();
+        fs::write(
+            res.join("values-zh/strings.xml"),
+            r#"<resources><string name="title">首页</string></resources>"#,
+        )
+        .unwrap();
+
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","zh":"中文"},"escapeOnly":{}}"#;
+        let parsed_cfg = ParsedCfg::from_json(cfg).unwrap();
+        let mut source = source::open_source(dir.to_str().unwrap(), &parsed_cfg).unwrap();
+        let parsed_cfg = read_excel::parse_cfg_with_source(source.as_mut(), parsed_cfg).unwrap();
+        assert_eq!(parsed_cfg.lang_index_map.len(), 2);
+        let maps: HashMap<String, HashMap<String, String>> =
+            read_excel::read_lang_maps(source.as_mut(), &parsed_cfg)
+                .unwrap()
+                .into_iter()
+                .collect();
+        assert_eq!(maps["zh"]["title"], "首页");
+        assert_eq!(maps["zh"]["count"], "");
+
+        let meta = read_excel::read_sheet_meta(source.as_mut(), &parsed_cfg).unwrap();
+        assert_eq!(meta.comment_map.len(), 1);
+        assert_eq!(meta.comment_map["title"], "首页标题");
+        assert_eq!(meta.translatable_map.get("app_id"), Some(&false));
+        assert_eq!(meta.formatted_map.get("count"), Some(&false));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_excel() {
+        let dir = std::env::temp_dir().join("excel_to_xml_test_export_excel");
+        fs::remove_dir_all(&dir).ok();
+        let res = dir.join("app/src/main/res");
+        fs::create_dir_all(res.join("values")).unwrap();
+        fs::create_dir_all(res.join("values-zh")).unwrap();
+        fs::write(
+            res.join("values/strings.xml"),
+            r#"<resources>
+    <string name="title">Home</string>
+    <string name="quote">Don\'t &amp; \"go\"</string>
+</resources>"#,
+        )
+        .unwrap();
+        fs::write(
+            res.join("values-zh/strings.xml"),
+            r#"<resources><string name="extra">多出</string><string name="title">首页</string></resources>"#,
+        )
+        .unwrap();
+
+        let cfg = r#"{"tagName":"Android tag","defaultLang":"en","langMap":{"en":"英语","zh":"中文简体"},"escapeOnly":{}}"#;
+        let excel_path = dir.join("strings.xlsx");
+        let excel_path = excel_path.to_str().unwrap();
+        export_excel(cfg, dir.to_str().unwrap(), excel_path).unwrap();
+
+        // 生成的表格可以直接作为update的数据源
+        let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg).unwrap();
+        assert_eq!(parsed_cfg.tag_index, 0);
+        assert_eq!(
+            parsed_cfg.lang_index_map,
+            vec![("en".to_string(), 1), ("zh".to_string(), 2)]
+        );
+        let mut source = source::open_source(excel_path, &parsed_cfg).unwrap();
+        assert_eq!(
+            source.header().unwrap(),
+            vec!["Android tag", "英语", "中文简体"]
+        );
+        let maps: HashMap<String, HashMap<String, String>> =
+            read_excel::read_lang_maps(source.as_mut(), &parsed_cfg)
+                .unwrap()
+                .into_iter()
+                .collect();
+        assert_eq!(maps["en"]["title"], "Home");
+        assert_eq!(maps["en"]["quote"], "Don't & \"go\"");
+        assert_eq!(maps["zh"]["title"], "首页");
+        assert_eq!(maps["zh"]["extra"], "多出");
+        assert_eq!(maps["en"]["extra"], "");
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,850 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use crate::{
+    config::ParsedCfg,
+    read_excel::ExcelSource,
+    read_xml::XmlSource,
+    validate::{self, KeyPolicy},
+    write_xlsx,
+    xlsx_package::{self, MergedRegion, SheetVisibility},
+};
+
+/// 数据源自带的与语言无关的标签信息，表格通过配置的列读取，不使用该字段
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RowMeta {
+    pub comment: Option<String>,    // 备注
+    pub translatable: Option<bool>, // 是否需要翻译，未标记时为None
+    pub formatted: Option<bool>,    // 是否为格式化字符串，未标记时为None
+}
+
+/// 数据源中的一行
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SourceRow {
+    pub row: u32,                    // 行号，从0开始，0为表头
+    pub tag: String,                 // 标签，已去除首尾空白
+    pub cells: HashMap<u32, String>, // 列序号 - 值，只包含请求的列中存在的单元格
+    pub meta: Option<RowMeta>,       // 数据源自带的标签信息，表格数据源为None
+}
+
+/// 翻译表数据源，表头用于解析配置，数据行供写入流程使用
+pub trait TranslationSource {
+    /// 读取表头
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /**
+     * 逐行读取数据，跳过表头和标签为空的行
+     * @param tag_index 标签列序号
+     * @param columns 需要读取的列序号
+     * @param visit 每读取一行调用一次
+     */
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>>;
+
+    /**
+     * 按位置读取单元格的值，不跳过标签为空、隐藏或删除线的行，用于读取合并区域左上角的值
+     * @param cells 单元格位置 (行, 列)
+     * @return 位置 - 值，不存在的单元格不包含，不支持按位置读取的数据源返回空映射
+     */
+    fn cell_values(
+        &mut self,
+        _cells: &HashSet<(u32, u32)>,
+    ) -> Result<HashMap<(u32, u32), String>, Box<dyn Error>> {
+        Ok(HashMap::new())
+    }
+}
+
+/// 根据路径打开数据源，文件夹读取其中的strings.xml，.csv 使用CSV，其余按Excel处理
+/// 配置了跳过隐藏和删除线时忽略对应的行和单元格，配置了合并单元格的列时填充合并区域，配置了行筛选条件时只读取满足条件的行，配置了标签名称检查时，数据行的标签按策略检查，重复的标签按策略取舍
+pub fn open_source(
+    path: &str,
+    parsed_cfg: &ParsedCfg,
+) -> Result<Box<dyn TranslationSource>, Box<dyn Error>> {
+    let is_dir = Path::new(path).is_dir();
+    let is_csv = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let is_xlsx = !is_dir && !is_csv;
+    let source: Box<dyn TranslationSource> = if is_dir {
+        Box::new(XmlSource::open(path, parsed_cfg)?)
+    } else if is_csv {
+        let content = fs::read_to_string(path)?;
+        Box::new(MemorySource::from_csv(&content))
+    } else if parsed_cfg.skip_hidden || parsed_cfg.skip_strikethrough {
+        let mut visibility = xlsx_package::read_sheet_visibility(path, &parsed_cfg.sheet_name)?;
+        if !parsed_cfg.skip_hidden {
+            visibility.hidden_rows.clear();
+            visibility.hidden_cols.clear();
+        }
+        if !parsed_cfg.skip_strikethrough {
+            visibility.struck_cells.clear();
+        }
+        Box::new(VisibleSource {
+            inner: Box::new(ExcelSource::open(
+                path,
+                &parsed_cfg.sheet_name,
+                &parsed_cfg.date_format,
+            )?),
+            visibility,
+            tag_name: parsed_cfg.tag_name.clone(),
+            reported: false,
+        })
+    } else {
+        Box::new(ExcelSource::open(
+            path,
+            &parsed_cfg.sheet_name,
+            &parsed_cfg.date_format,
+        )?)
+    };
+    let source: Box<dyn TranslationSource> = if !is_xlsx || parsed_cfg.merged_columns.is_empty() {
+        source
+    } else {
+        Box::new(MergedSource {
+            inner: source,
+            regions: xlsx_package::read_merged_regions(path, &parsed_cfg.sheet_name)?,
+            merged_columns: parsed_cfg.merged_columns.clone(),
+            anchors: None,
+            reported: false,
+        })
+    };
+    let source: Box<dyn TranslationSource> = if parsed_cfg.row_filters.is_empty() {
+        source
+    } else {
+        let filters = parsed_cfg
+            .row_filters
+            .iter()
+            .map(|filter| RowFilter::parse(filter))
+            .collect::<Result<_, _>>()?;
+        Box::new(FilteredSource {
+            inner: source,
+            filters,
+            reported: false,
+        })
+    };
+    // 标签名称规则来自Android资源名称，只对android生效
+    let policy = KeyPolicy::parse(&parsed_cfg.key_check);
+    let is_android = matches!(parsed_cfg.target.as_str(), "" | "android");
+    let source: Box<dyn TranslationSource> = if policy == KeyPolicy::Off || !is_android {
+        source
+    } else {
+        Box::new(KeyCheckedSource {
+            inner: source,
+            policy,
+            reported: false,
+        })
+    };
+    Ok(check_duplicates(source, parsed_cfg))
+}
+
+/**
+ * 为数据源加上重复标签检查，按配置的duplicateTag处理重复的标签
+ * @param source 数据源
+ * @param parsed_cfg 解析后的配置
+ */
+pub fn check_duplicates(
+    source: Box<dyn TranslationSource>,
+    parsed_cfg: &ParsedCfg,
+) -> Box<dyn TranslationSource> {
+    Box::new(DuplicateCheckedSource {
+        inner: source,
+        policy: DuplicatePolicy::parse(&parsed_cfg.duplicate_tag),
+        report_columns: parsed_cfg
+            .lang_index_map
+            .iter()
+            .map(|(_, index)| *index)
+            .collect(),
+        dropped: None,
+    })
+}
+
+/// 忽略隐藏行、隐藏列和删除线单元格的数据源
+/// 隐藏行和标签带删除线的行整行跳过，带删除线的值按空单元格处理
+/// 隐藏列在表头中按空白处理，相当于没有这一列，标签列不受影响
+pub struct VisibleSource {
+    inner: Box<dyn TranslationSource>,
+    visibility: SheetVisibility,
+    tag_name: String,
+    reported: bool,
+}
+
+impl TranslationSource for VisibleSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut header = self.inner.header()?;
+        for (col, name) in header.iter_mut().enumerate() {
+            if self.visibility.hidden_cols.contains(&(col as u32)) && *name != self.tag_name {
+                name.clear();
+            }
+        }
+        Ok(header)
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let visibility = &self.visibility;
+        let report = !self.reported;
+        let mut skipped_rows = 0;
+        let mut skipped_cells = 0;
+        self.inner.read_rows(tag_index, columns, &mut |mut row| {
+            if visibility.hidden_rows.contains(&row.row)
+                || visibility.struck_cells.contains(&(row.row, tag_index))
+            {
+                skipped_rows += 1;
+                return;
+            }
+            let before = row.cells.len();
+            row.cells
+                .retain(|col, _| !visibility.struck_cells.contains(&(row.row, *col)));
+            skipped_cells += before - row.cells.len();
+            visit(row);
+        })?;
+        if report && skipped_rows + skipped_cells > 0 {
+            println!(
+                "[隐藏] 跳过{}行隐藏或删除线的数据，{}个删除线的单元格",
+                skipped_rows, skipped_cells
+            );
+        }
+        self.reported = true;
+        Ok(())
+    }
+
+    fn cell_values(
+        &mut self,
+        cells: &HashSet<(u32, u32)>,
+    ) -> Result<HashMap<(u32, u32), String>, Box<dyn Error>> {
+        self.inner.cell_values(cells)
+    }
+}
+
+/// 填充合并单元格的数据源，合并区域左上角的值填充到区域内配置的列中为空的单元格
+/// 左上角的值按位置读取，所在行被跳过时同样填充；标签列不填充，没有标签的行不会读取
+pub struct MergedSource {
+    inner: Box<dyn TranslationSource>,
+    regions: Vec<MergedRegion>,
+    merged_columns: Vec<String>,                  // 列名称，* 对应所有列
+    anchors: Option<HashMap<(u32, u32), String>>, // 左上角位置 - 值，第一次读取时记录
+    reported: bool,
+}
+
+impl TranslationSource for MergedSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.inner.header()
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let header = self.inner.header()?;
+        let fill_all = self.merged_columns.iter().any(|name| name == "*");
+        let fill_columns: Vec<u32> = columns
+            .iter()
+            .copied()
+            .filter(|col| *col != tag_index)
+            .filter(|col| {
+                fill_all
+                    || header
+                        .get(*col as usize)
+                        .is_some_and(|name| self.merged_columns.contains(name))
+            })
+            .collect();
+        // 与需要填充的列相交的区域
+        let regions: Vec<&MergedRegion> = self
+            .regions
+            .iter()
+            .filter(|region| {
+                fill_columns
+                    .iter()
+                    .any(|col| (region.first_col..=region.last_col).contains(col))
+            })
+            .collect();
+        if self.anchors.is_none() {
+            let cells = self
+                .regions
+                .iter()
+                .map(|region| (region.first_row, region.first_col))
+                .collect();
+            self.anchors = Some(self.inner.cell_values(&cells)?);
+        }
+        let anchors = self.anchors.as_ref().unwrap();
+
+        let mut filled = 0;
+        self.inner.read_rows(tag_index, columns, &mut |mut row| {
+            for region in &regions {
+                let anchor = match anchors.get(&(region.first_row, region.first_col)) {
+                    Some(anchor) => anchor,
+                    None => continue,
+                };
+                for col in &fill_columns {
+                    if !region.contains(row.row, *col) {
+                        continue;
+                    }
+                    let cell = row.cells.entry(*col).or_default();
+                    if cell.trim().is_empty() {
+                        *cell = anchor.clone();
+                        filled += 1;
+                    }
+                }
+            }
+            visit(row);
+        })?;
+        if !self.reported && filled > 0 {
+            println!("[合并单元格] 填充了{}个单元格", filled);
+        }
+        self.reported = true;
+        Ok(())
+    }
+}
+
+/// 行筛选的比较方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOp {
+    Equals,      // ==，去除首尾空白后相等
+    NotEquals,   // !=
+    Contains,    // contains，忽略大小写包含
+    NotContains, // !contains
+}
+
+/// 行筛选条件，如 Platform contains Android、Status == Approved
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFilter {
+    pub column: String, // 表头中的列名称
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl RowFilter {
+    /// 解析筛选条件，格式为 列名称 比较方式 值，列名称可以包含空格
+    pub fn parse(expr: &str) -> Result<RowFilter, Box<dyn Error>> {
+        const OPS: [(&str, FilterOp); 4] = [
+            (" == ", FilterOp::Equals),
+            (" != ", FilterOp::NotEquals),
+            (" !contains ", FilterOp::NotContains),
+            (" contains ", FilterOp::Contains),
+        ];
+        OPS.iter()
+            .filter_map(|(token, op)| expr.find(token).map(|pos| (pos, token, op)))
+            .min_by_key(|(pos, _, _)| *pos)
+            .map(|(pos, token, op)| RowFilter {
+                column: expr[..pos].trim().to_string(),
+                op: *op,
+                value: expr[pos + token.len()..].trim().to_string(),
+            })
+            .filter(|filter| !filter.column.is_empty())
+            .ok_or_else(|| format!("无法解析筛选条件: {}", expr).into())
+    }
+
+    /// 判断单元格的值是否满足条件，单元格不存在时按空字符串处理
+    pub fn matches(&self, cell: &str) -> bool {
+        let cell = cell.trim();
+        match self.op {
+            FilterOp::Equals => cell == self.value,
+            FilterOp::NotEquals => cell != self.value,
+            FilterOp::Contains => cell.to_lowercase().contains(&self.value.to_lowercase()),
+            FilterOp::NotContains => !cell.to_lowercase().contains(&self.value.to_lowercase()),
+        }
+    }
+}
+
+/// 按条件筛选行的数据源，筛选列随数据行一起读取，输出前移除
+pub struct FilteredSource {
+    inner: Box<dyn TranslationSource>,
+    filters: Vec<RowFilter>,
+    reported: bool,
+}
+
+impl TranslationSource for FilteredSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.inner.header()
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let header = self.inner.header()?;
+        let filter_columns = self
+            .filters
+            .iter()
+            .map(|filter| {
+                header
+                    .iter()
+                    .position(|name| *name == filter.column)
+                    .map(|index| index as u32)
+                    .ok_or_else(|| format!("未找到筛选列: {}", filter.column))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+        let mut all_columns = columns.to_vec();
+        all_columns.extend(filter_columns.iter().filter(|col| !columns.contains(col)));
+
+        let filters = &self.filters;
+        let mut skipped = 0;
+        self.inner
+            .read_rows(tag_index, &all_columns, &mut |mut row| {
+                let accepted = filters.iter().zip(&filter_columns).all(|(filter, col)| {
+                    filter.matches(row.cells.get(col).map(String::as_str).unwrap_or(""))
+                });
+                if !accepted {
+                    skipped += 1;
+                    return;
+                }
+                row.cells.retain(|col, _| columns.contains(col));
+                visit(row);
+            })?;
+        if !self.reported && skipped > 0 {
+            println!("[筛选] 跳过{}行不满足条件的数据", skipped);
+        }
+        self.reported = true;
+        Ok(())
+    }
+}
+
+/// 检查标签名称的数据源，不合法的标签按策略报告、跳过或转换
+/// 同一个数据源会被读取多次，问题只在第一次读取时输出
+pub struct KeyCheckedSource {
+    inner: Box<dyn TranslationSource>,
+    policy: KeyPolicy,
+    reported: bool,
+}
+
+impl TranslationSource for KeyCheckedSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.inner.header()
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let policy = self.policy;
+        let report = !self.reported;
+        let mut bad_count = 0;
+        // 输出的标签 - 原标签，用于发现转换后与其他标签重名
+        let mut origins: HashMap<String, String> = HashMap::new();
+        let mut emit = |row: SourceRow, origin: String| {
+            if report && policy == KeyPolicy::Sanitize {
+                match origins.get(&row.tag) {
+                    Some(other) if *other != origin => println!(
+                        "[标签名称] {} (第{}行): 转换后的 {} 与 {} 重名",
+                        origin,
+                        row.row + 1,
+                        row.tag,
+                        other
+                    ),
+                    Some(_) => {}
+                    None => {
+                        origins.insert(row.tag.clone(), origin);
+                    }
+                }
+            }
+            visit(row);
+        };
+        self.inner.read_rows(tag_index, columns, &mut |mut row| {
+            let problem = match validate::check_key(&row.tag) {
+                Some(problem) => problem,
+                None => {
+                    let origin = row.tag.clone();
+                    return emit(row, origin);
+                }
+            };
+            bad_count += 1;
+            let key = match policy {
+                KeyPolicy::Sanitize => validate::sanitize_key(&row.tag),
+                KeyPolicy::Skip | KeyPolicy::Error => None,
+                _ => Some(row.tag.clone()),
+            };
+            if report {
+                let action = match (&key, policy) {
+                    (Some(key), KeyPolicy::Sanitize) => format!("转换为 {}", key),
+                    (_, KeyPolicy::Warn) => "仍然写入".to_string(),
+                    (_, KeyPolicy::Error) => "中止同步".to_string(),
+                    _ => "跳过".to_string(),
+                };
+                println!(
+                    "[标签名称] {} (第{}行): {}，{}",
+                    row.tag,
+                    row.row + 1,
+                    problem,
+                    action
+                );
+            }
+            if let Some(key) = key {
+                let origin = std::mem::replace(&mut row.tag, key);
+                emit(row, origin);
+            }
+        })?;
+        self.reported = true;
+        if policy == KeyPolicy::Error && bad_count > 0 {
+            return Err(format!("标签名称检查未通过，共{}个不合法的标签", bad_count).into());
+        }
+        Ok(())
+    }
+}
+
+/// 重复标签的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    First, // 使用第一次出现的行
+    Last,  // 使用最后一次出现的行
+    Abort, // 存在重复时中止
+}
+
+impl DuplicatePolicy {
+    /// 配置中可以使用的值
+    pub const VALUES: [&'static str; 3] = ["first", "last", "abort"];
+
+    /// 解析配置的值，配置解析时已检查过可选值
+    pub fn parse(value: &str) -> DuplicatePolicy {
+        match value {
+            "first" => DuplicatePolicy::First,
+            "abort" => DuplicatePolicy::Abort,
+            _ => DuplicatePolicy::Last,
+        }
+    }
+}
+
+/// 检查重复标签的数据源，同一个标签只保留一行，报告重复的行号和不同的值
+/// 第一次读取时先只读取标签找出重复，只为重复的行读取报告用的列，之后的读取按记录的行号跳过未保留的行
+pub struct DuplicateCheckedSource {
+    inner: Box<dyn TranslationSource>,
+    policy: DuplicatePolicy,
+    report_columns: Vec<u32>,      // 报告重复行中不同的值时比较的列
+    dropped: Option<HashSet<u32>>, // 未保留的行号，第一次读取后记录
+}
+
+impl DuplicateCheckedSource {
+    /// 找出重复的标签并输出报告，返回未保留的行号
+    fn find_duplicates(&mut self, tag_index: u32) -> Result<HashSet<u32>, Box<dyn Error>> {
+        // 标签 - 出现的行号，只读取标签列
+        let mut positions: HashMap<String, Vec<u32>> = HashMap::new();
+        self.inner.read_rows(tag_index, &[], &mut |row| {
+            positions.entry(row.tag).or_default().push(row.row)
+        })?;
+        let mut duplicates: Vec<Vec<u32>> = positions
+            .into_values()
+            .filter(|rows| rows.len() > 1)
+            .collect();
+        let mut dropped = HashSet::new();
+        if duplicates.is_empty() {
+            return Ok(dropped);
+        }
+        duplicates.sort();
+
+        // 只保留重复的行，用于报告不同的值
+        let duplicate_rows: HashSet<u32> = duplicates.iter().flatten().copied().collect();
+        let report_columns: Vec<u32> = self
+            .report_columns
+            .iter()
+            .copied()
+            .filter(|col| *col != tag_index)
+            .collect();
+        let mut rows = HashMap::new();
+        self.inner
+            .read_rows(tag_index, &report_columns, &mut |row| {
+                if duplicate_rows.contains(&row.row) {
+                    rows.insert(row.row, row);
+                }
+            })?;
+
+        for group in &duplicates {
+            let kept = match self.policy {
+                DuplicatePolicy::First => group[0],
+                _ => group[group.len() - 1],
+            };
+            dropped.extend(group.iter().filter(|row| **row != kept));
+            report_duplicate(&rows, group, &report_columns, self.policy, kept);
+        }
+        if self.policy == DuplicatePolicy::Abort {
+            return Err(format!("存在重复的标签，共{}个", duplicates.len()).into());
+        }
+        Ok(dropped)
+    }
+}
+
+impl TranslationSource for DuplicateCheckedSource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.inner.header()
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        let dropped = match self.dropped.take() {
+            Some(dropped) => dropped,
+            None => self.find_duplicates(tag_index)?,
+        };
+        let result = self.inner.read_rows(tag_index, columns, &mut |row| {
+            if !dropped.contains(&row.row) {
+                visit(row)
+            }
+        });
+        self.dropped = Some(dropped);
+        result
+    }
+}
+
+/// 输出重复标签的行号，以及与第一次出现时不同的值，rows为 行号 - 重复的行，group为同一个标签出现的行号
+fn report_duplicate(
+    rows: &HashMap<u32, SourceRow>,
+    group: &[u32],
+    columns: &[u32],
+    policy: DuplicatePolicy,
+    kept: u32,
+) {
+    let first = &rows[&group[0]];
+    for row_index in &group[1..] {
+        let row = &rows[row_index];
+        let diffs: Vec<String> = columns
+            .iter()
+            .filter_map(|col| {
+                let a = first.cells.get(col).map(String::as_str).unwrap_or("");
+                let b = row.cells.get(col).map(String::as_str).unwrap_or("");
+                (a != b).then(|| format!("{}列 {:?} / {:?}", write_xlsx::column_name(*col), a, b))
+            })
+            .collect();
+        let diff = if diffs.is_empty() {
+            "值相同".to_string()
+        } else {
+            diffs.join("，")
+        };
+        println!(
+            "[重复标签] {} (第{}行, 第{}行): {}",
+            row.tag,
+            first.row + 1,
+            row.row + 1,
+            diff
+        );
+    }
+    let action = match policy {
+        DuplicatePolicy::Abort => "中止同步".to_string(),
+        _ => format!("使用第{}行", kept + 1),
+    };
+    println!("[重复标签] {}: {}", first.tag, action);
+}
+
+/// 内存中的表格数据源，第一行为表头
+#[derive(Debug, Default)]
+pub struct MemorySource {
+    rows: Vec<Vec<String>>,
+}
+
+impl MemorySource {
+    pub fn new(rows: Vec<Vec<String>>) -> Self {
+        MemorySource { rows }
+    }
+
+    /// 解析CSV内容，支持引号包裹的逗号、换行和 "" 转义
+    pub fn from_csv(content: &str) -> Self {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let mut rows = Vec::new();
+        let mut row: Vec<String> = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = content.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        field.push('"');
+                        chars.next();
+                    }
+                    '"' => in_quotes = false,
+                    _ => field.push(c),
+                }
+                continue;
+            }
+            match c {
+                '"' => in_quotes = true,
+                ',' => row.push(std::mem::take(&mut field)),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                _ => field.push(c),
+            }
+        }
+        if !field.is_empty() || !row.is_empty() {
+            row.push(field);
+            rows.push(row);
+        }
+        Self::new(rows)
+    }
+}
+
+impl TranslationSource for MemorySource {
+    fn header(&mut self) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(self.rows.first().cloned().unwrap_or_default())
+    }
+
+    fn read_rows(
+        &mut self,
+        tag_index: u32,
+        columns: &[u32],
+        visit: &mut dyn FnMut(SourceRow),
+    ) -> Result<(), Box<dyn Error>> {
+        for (row, cells) in self.rows.iter().enumerate().skip(1) {
+            let tag = match cells.get(tag_index as usize) {
+                Some(tag) if !tag.trim().is_empty() => tag.trim().to_string(),
+                _ => continue,
+            };
+            let cells = columns
+                .iter()
+                .filter_map(|col| cells.get(*col as usize).map(|v| (*col, v.clone())))
+                .collect();
+            visit(SourceRow {
+                row: row as u32,
+                tag,
+                cells,
+                meta: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn cell_values(
+        &mut self,
+        cells: &HashSet<(u32, u32)>,
+    ) -> Result<HashMap<(u32, u32), String>, Box<dyn Error>> {
+        Ok(cells
+            .iter()
+            .filter_map(|&(row, col)| {
+                let value = self.rows.get(row as usize)?.get(col as usize)?;
+                Some(((row, col), value.clone()))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_excel;
+
+    #[test]
+    fn test_csv_source() {
+        let csv = "\u{feff}Android tag,英语,中文简体\r\napp_name,App,应用\r\n\"hello\",\"Hello, \"\"world\"\"\nline2\",你好\r\n,orphan,孤儿\r\nshort,Short\r\n";
+        let mut source = MemorySource::from_csv(csv);
+        assert_eq!(
+            source.header().unwrap(),
+            vec!["Android tag", "英语", "中文简体"]
+        );
+
+        let mut tag_value_map = HashMap::new();
+        read_excel::process_excel_multi_lang(&mut source, 0, vec![1, 2], &mut tag_value_map)
+            .unwrap();
+        assert_eq!(tag_value_map.len(), 3);
+        assert_eq!(tag_value_map["hello"][&1], "Hello, \"world\"\nline2");
+        assert_eq!(tag_value_map["app_name"][&2], "应用");
+        assert!(!tag_value_map["short"].contains_key(&2));
+    }
+
+    #[test]
+    fn test_row_filter() {
+        let filter = RowFilter::parse("Android tag != x").unwrap();
+        assert_eq!(filter.column, "Android tag");
+        assert_eq!(filter.op, FilterOp::NotEquals);
+        assert!(RowFilter::parse("Platform").is_err());
+
+        let csv = "tag,en,Platform,Status\na,1,Android/iOS,Approved\nb,2,iOS,Approved\nc,3,android,Draft\n";
+        let mut source = FilteredSource {
+            inner: Box::new(MemorySource::from_csv(csv)),
+            filters: vec![
+                RowFilter::parse("Platform contains Android").unwrap(),
+                RowFilter::parse("Status == Approved").unwrap(),
+            ],
+            reported: false,
+        };
+        let mut rows = Vec::new();
+        source
+            .read_rows(0, &[1], &mut |row| rows.push((row.tag, row.cells)))
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, "a");
+        assert_eq!(rows[0].1.len(), 1);
+    }
+
+    #[test]
+    fn test_merged_source() {
+        // 纵向合并的左上角所在行没有标签，横向合并在同一行内填充
+        let csv = "tag,Platform,en,zh\n,All,,\na,,A,甲\nb,,B,\nc,iOS,Both,\n";
+        let region = |first_row, first_col, last_row, last_col| MergedRegion {
+            first_row,
+            first_col,
+            last_row,
+            last_col,
+        };
+        let mut source = MergedSource {
+            inner: Box::new(MemorySource::from_csv(csv)),
+            regions: vec![region(1, 1, 3, 1), region(4, 2, 4, 3)],
+            merged_columns: vec!["*".to_string()],
+            anchors: None,
+            reported: false,
+        };
+        let mut rows = Vec::new();
+        source
+            .read_rows(0, &[1, 3], &mut |row| rows.push((row.tag, row.cells)))
+            .unwrap();
+        let cell = |i: usize, col: u32| rows[i].1.get(&col).cloned().unwrap_or_default();
+        assert_eq!(rows.len(), 3);
+        assert_eq!((cell(0, 1), cell(1, 1)), ("All".into(), "All".into()));
+        assert_eq!((cell(0, 3), cell(1, 3)), ("甲".into(), String::new()));
+        assert_eq!((cell(2, 1), cell(2, 3)), ("iOS".into(), "Both".into()));
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        let csv = "tag,en\na,1\nb,2\na,3\n";
+        let read = |policy: DuplicatePolicy| {
+            let mut source = DuplicateCheckedSource {
+                inner: Box::new(MemorySource::from_csv(csv)),
+                policy,
+                report_columns: vec![1],
+                dropped: None,
+            };
+            let mut rows = Vec::new();
+            source.read_rows(0, &[1], &mut |row| {
+                rows.push((row.tag, row.cells[&1].clone()))
+            })?;
+            // 再次读取时使用第一次记录的结果
+            let mut again = Vec::new();
+            source.read_rows(0, &[1], &mut |row| {
+                again.push((row.tag, row.cells[&1].clone()))
+            })?;
+            assert_eq!(rows, again);
+            Ok::<_, Box<dyn Error>>(rows)
+        };
+        let first = read(DuplicatePolicy::First).unwrap();
+        assert_eq!(
+            first,
+            vec![("a".into(), "1".into()), ("b".into(), "2".into())]
+        );
+        let last = read(DuplicatePolicy::Last).unwrap();
+        assert_eq!(
+            last,
+            vec![("b".into(), "2".into()), ("a".into(), "3".into())]
+        );
+        assert!(read(DuplicatePolicy::Abort).is_err());
+    }
+}
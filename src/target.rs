@@ -0,0 +1,372 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::Path,
+};
+
+use crate::{
+    config::ParsedCfg, read_excel, source, validate, write_harmony::HarmonyTarget,
+    write_po::PoTarget, write_properties::PropertiesTarget, write_resx::ResxTarget,
+    write_xliff::XliffTarget, write_xml::AndroidTarget,
+};
+
+/// 文件中的条目 标签 - 值，保持文件中的顺序
+pub type Entries = Vec<(String, String)>;
+
+/// 读取单个语言的 标签 - 值 映射，数据源中没有该语言时返回None
+pub type LoadLang<'a> =
+    dyn FnMut(&str) -> Result<Option<HashMap<String, String>>, Box<dyn Error>> + 'a;
+
+/// 表格中与语言无关的标签信息
+#[derive(Debug, Default)]
+pub struct SheetMeta {
+    pub comment_map: HashMap<String, String>,    // 标签 - 备注
+    pub row_map: HashMap<String, u32>,           // 标签 - 行号，从0开始
+    pub max_length_map: HashMap<String, usize>,  // 标签 - 最大长度
+    pub translatable_map: HashMap<String, bool>, // 标签 - 是否需要翻译，未标记的不包含
+    pub formatted_map: HashMap<String, bool>,    // 标签 - 是否为格式化字符串，未标记的不包含
+}
+
+/// 写入单个语言时使用的数据
+pub struct LangEntries<'a> {
+    pub lang: &'a str,                                  // 当前语言
+    pub tag_value_map: &'a HashMap<String, String>,     // 当前语言 标签 - 值
+    pub default_value_map: &'a HashMap<String, String>, // 默认语言 标签 - 值
+    pub meta: &'a SheetMeta,                            // 标签信息
+}
+
+/// 输出目标，每种资源格式实现一次，共用同一套按语言写入的流程
+pub trait LocalizationTarget {
+    /// 查找目标文件，返回 语言 - 文件路径，文件可以尚不存在
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>>;
+
+    /// 读取文件中已有的条目，值为还原转义后的文本
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>>;
+
+    /// 作为数据源时读取默认语言的条目，用于默认语言没有单独文件的格式
+    /// @param files discover找到的 语言 - 文件路径
+    fn read_default_entries(&self, _files: &Entries) -> Result<Option<Entries>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    /// 不写入该文件的标签，如标记为不需要翻译的，检查和写入前排除
+    fn skipped_tags(
+        &self,
+        _path: &str,
+        _entries: &LangEntries,
+        _parsed_cfg: &ParsedCfg,
+    ) -> Result<HashSet<String>, Box<dyn Error>> {
+        Ok(HashSet::new())
+    }
+
+    /// 将标签值映射应用到文件并写入
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>>;
+}
+
+/// 根据配置中的target创建输出目标
+pub fn create_target(
+    parsed_cfg: &ParsedCfg,
+) -> Result<Box<dyn LocalizationTarget>, Box<dyn Error>> {
+    match parsed_cfg.target.as_str() {
+        "" | "android" => Ok(Box::new(AndroidTarget)),
+        "harmony" => Ok(Box::new(HarmonyTarget)),
+        "properties" => Ok(Box::new(PropertiesTarget)),
+        "resx" => Ok(Box::new(ResxTarget)),
+        "po" => Ok(Box::new(PoTarget)),
+        "xliff" => Ok(Box::new(XliffTarget)),
+        other => Err(format!("不支持的输出目标: {}", other).into()),
+    }
+}
+
+/**
+ * 按语言写入目标文件，默认语言优先读取，以便空值使用默认语言替换
+ * @param target 输出目标
+ * @param parsed_cfg 解析后的配置
+ * @param dir 目标所在路径
+ * @param meta 标签信息，数据源不是表格时为空
+ * @param load_lang 读取语言的 标签 - 值 映射，数据源中没有该语言时返回None，检查和写入时可能各调用一次
+ * @param keep_going 写入单个文件失败时输出日志并继续处理其余文件，全部处理完后返回错误
+ */
+pub fn sync(
+    target: &dyn LocalizationTarget,
+    parsed_cfg: &ParsedCfg,
+    dir: &str,
+    meta: &SheetMeta,
+    load_lang: &mut LoadLang,
+    keep_going: bool,
+) -> Result<(), Box<dyn Error>> {
+    let files = target.discover(dir, parsed_cfg)?;
+    let default_lang = &parsed_cfg.default_lang;
+    let default_value_map = load_lang(default_lang)?;
+    let empty_map = HashMap::new();
+    let default_map = default_value_map.as_ref().unwrap_or(&empty_map);
+
+    // 有检查配置为error时，先检查全部语言，都通过后再写入，避免部分文件已被改写
+    let check_first = validate::has_error_policy(parsed_cfg);
+    // 检查时读取的语言，写入时直接使用，不再重复读取
+    let mut checked_maps: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    if check_first {
+        let mut errors = Vec::new();
+        for (lang, path) in &files {
+            let tag_value_map = if lang == default_lang {
+                match &default_value_map {
+                    Some(map) => map,
+                    None => continue,
+                }
+            } else {
+                if !checked_maps.contains_key(lang.as_str()) {
+                    match load_lang(lang)? {
+                        Some(map) => checked_maps.insert(lang, map),
+                        None => continue,
+                    };
+                }
+                &checked_maps[lang.as_str()]
+            };
+            let entries = LangEntries {
+                lang,
+                tag_value_map,
+                default_value_map: default_map,
+                meta,
+            };
+            let filtered = exclude_skipped(target, path, &entries, parsed_cfg, false)?;
+            let entries = LangEntries {
+                tag_value_map: filtered.as_ref().unwrap_or(tag_value_map),
+                ..entries
+            };
+            if let Err(e) = validate::check_lang(parsed_cfg, &entries) {
+                errors.push(e.to_string());
+            }
+        }
+        if !errors.is_empty() {
+            return Err(format!("检查未通过，未写入任何文件: {}", errors.join("; ")).into());
+        }
+    }
+
+    // 写入失败的语言
+    let mut failed = Vec::new();
+    for (lang, path) in &files {
+        let loaded;
+        let tag_value_map = if lang == default_lang {
+            match &default_value_map {
+                Some(map) => map,
+                None => continue, // 数据源中没有默认语言，跳过
+            }
+        } else if check_first {
+            match checked_maps.get(lang.as_str()) {
+                Some(map) => map,
+                None => continue, // 数据源中没有这个语言，跳过
+            }
+        } else {
+            loaded = match load_lang(lang)? {
+                Some(map) => map,
+                None => continue, // 数据源中没有这个语言，跳过
+            };
+            &loaded
+        };
+        let entries = LangEntries {
+            lang,
+            tag_value_map,
+            default_value_map: default_map,
+            meta,
+        };
+        let filtered = exclude_skipped(target, path, &entries, parsed_cfg, true)?;
+        let entries = LangEntries {
+            tag_value_map: filtered.as_ref().unwrap_or(tag_value_map),
+            ..entries
+        };
+        // 已提前检查过的不再重复输出
+        if !check_first {
+            validate::check_lang(parsed_cfg, &entries)?;
+        }
+        if let Err(e) = target.apply(path, &entries, parsed_cfg) {
+            if !keep_going {
+                return Err(e);
+            }
+            println!("更新文件失败, lang: {}, err: {:?}", lang, e);
+            failed.push(lang.as_str());
+        }
+    }
+    if !failed.is_empty() {
+        return Err(format!("{}个文件更新失败: {}", failed.len(), failed.join(", ")).into());
+    }
+    Ok(())
+}
+
+/**
+ * 排除输出目标中不写入的标签
+ * @param report 是否输出跳过的标签
+ * @return 排除后的 标签 - 值，没有需要排除的标签时返回None
+ */
+fn exclude_skipped(
+    target: &dyn LocalizationTarget,
+    path: &str,
+    entries: &LangEntries,
+    parsed_cfg: &ParsedCfg,
+    report: bool,
+) -> Result<Option<HashMap<String, String>>, Box<dyn Error>> {
+    let skipped = target.skipped_tags(path, entries, parsed_cfg)?;
+    let mut skipped_tags: Vec<&String> = entries
+        .tag_value_map
+        .keys()
+        .filter(|tag| skipped.contains(*tag))
+        .collect();
+    if skipped_tags.is_empty() {
+        return Ok(None);
+    }
+    if report {
+        skipped_tags.sort();
+        println!(
+            "[不翻译] {}: 跳过{}个不需要翻译的标签: {}",
+            path,
+            skipped_tags.len(),
+            skipped_tags
+                .iter()
+                .map(|tag| tag.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(Some(
+        entries
+            .tag_value_map
+            .iter()
+            .filter(|(tag, _)| !skipped.contains(*tag))
+            .map(|(tag, value)| (tag.clone(), value.clone()))
+            .collect(),
+    ))
+}
+
+/// 解析配置，并输出日志
+fn parse_cfg(cfg_json: &str, excel_path: &str) -> Result<ParsedCfg, Box<dyn Error>> {
+    let cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json);
+    if cfg.is_err() {
+        println!("解析配置时出错: {:?}", cfg.err());
+        return Err("解析配置时出错".into());
+    }
+    let parsed_cfg = cfg.unwrap();
+    println!("解析配置成功: {:?}", parsed_cfg);
+    Ok(parsed_cfg)
+}
+
+/// 更新配置的输出目标，每次只解析一个语种
+pub fn update(cfg_json: &str, excel_path: &str, dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = parse_cfg(cfg_json, excel_path)?;
+    let target = create_target(&parsed_cfg)?;
+    update_target(target.as_ref(), &parsed_cfg, excel_path, dir)
+}
+
+/// 快速更新配置的输出目标，占用更多内存
+pub fn quick_update(cfg_json: &str, excel_path: &str, dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = parse_cfg(cfg_json, excel_path)?;
+    let target = create_target(&parsed_cfg)?;
+    quick_update_target(target.as_ref(), &parsed_cfg, excel_path, dir)
+}
+
+/// 逐语言读取Excel并写入目标
+/// 内存占用低，解析全部语言更耗时
+pub fn update_target(
+    target: &dyn LocalizationTarget,
+    parsed_cfg: &ParsedCfg,
+    excel_path: &str,
+    dir: &str,
+) -> Result<(), Box<dyn Error>> {
+    // 预先打开数据源，只打开一次
+    let mut source = source::open_source(excel_path, parsed_cfg)?;
+    let meta = read_excel::read_sheet_meta(source.as_mut(), parsed_cfg)?;
+
+    sync(
+        target,
+        parsed_cfg,
+        dir,
+        &meta,
+        &mut |lang| {
+            let lang_index = match parsed_cfg.lang_index_map.iter().find(|(l, _)| l == lang) {
+                Some((_, index)) => *index,
+                None => return Ok(None),
+            };
+            let mut tag_value_map = HashMap::with_capacity(5000);
+            read_excel::process_excel_single_lang(
+                source.as_mut(),
+                parsed_cfg.tag_index,
+                lang_index,
+                &mut tag_value_map,
+            )?;
+            Ok(Some(tag_value_map))
+        },
+        false,
+    )
+}
+
+/// 一次读取所有语种并写入目标，单个文件写入失败时继续处理其余文件，最后返回错误
+/// 解析全部语言耗时更少，但内存占用更高
+pub fn quick_update_target(
+    target: &dyn LocalizationTarget,
+    parsed_cfg: &ParsedCfg,
+    excel_path: &str,
+    dir: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut source = source::open_source(excel_path, parsed_cfg)?;
+    let lang_maps: HashMap<String, HashMap<String, String>> =
+        read_excel::read_lang_maps(source.as_mut(), parsed_cfg)?
+            .into_iter()
+            .collect();
+    let meta = read_excel::read_sheet_meta(source.as_mut(), parsed_cfg)?;
+    sync(
+        target,
+        parsed_cfg,
+        dir,
+        &meta,
+        &mut |lang| Ok(lang_maps.get(lang).cloned()),
+        true,
+    )
+}
+
+/**
+ * 使用另一种格式的文件作为数据源更新配置的输出目标
+ * @param source 数据源格式，通过discover查找文件、read_entries读取条目
+ * @param cfg_json 配置JSON
+ * @param source_dir 数据源文件所在文件夹
+ * @param dir 目标所在路径
+ */
+pub fn update_from_target(
+    source: &dyn LocalizationTarget,
+    cfg_json: &str,
+    source_dir: &str,
+    dir: &str,
+) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = ParsedCfg::from_json(cfg_json)?;
+    let target = create_target(&parsed_cfg)?;
+    let discovered = source.discover(source_dir, &parsed_cfg)?;
+    let source_files: HashMap<&str, &str> = discovered
+        .iter()
+        .map(|(lang, path)| (lang.as_str(), path.as_str()))
+        .collect();
+
+    sync(
+        target.as_ref(),
+        &parsed_cfg,
+        dir,
+        &SheetMeta::default(),
+        &mut |lang| match source_files.get(lang) {
+            Some(path) if Path::new(path).is_file() => {
+                Ok(Some(source.read_entries(path)?.into_iter().collect()))
+            }
+            _ => {
+                // 默认语言没有单独的文件时，由数据源提供
+                if lang == parsed_cfg.default_lang {
+                    if let Some(entries) = source.read_default_entries(&discovered)? {
+                        return Ok(Some(entries.into_iter().collect()));
+                    }
+                }
+                println!("未找到 {} 的数据源文件", lang);
+                Ok(None)
+            }
+        },
+        false,
+    )
+}
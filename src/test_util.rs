@@ -0,0 +1,14 @@
+use crate::{config::ParsedCfg, read_excel, source::MemorySource};
+
+/**
+ * 由CSV内容创建数据源，并按表头解析配置，测试中代替表格文件
+ * @param cfg_json 配置JSON
+ * @param csv CSV内容，第一行为表头
+ * @return 解析后的配置，数据源
+ */
+pub fn csv_source(cfg_json: &str, csv: &str) -> (ParsedCfg, MemorySource) {
+    let mut source = MemorySource::from_csv(csv);
+    let parsed_cfg = ParsedCfg::from_json(cfg_json).unwrap();
+    let parsed_cfg = read_excel::parse_cfg_with_source(&mut source, parsed_cfg).unwrap();
+    (parsed_cfg, source)
+}
@@ -0,0 +1,803 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    sync::LazyLock,
+};
+
+use crate::{
+    config::ParsedCfg,
+    read_excel, source,
+    target::{LangEntries, SheetMeta},
+    write_xlsx, write_xml,
+};
+use quick_xml::{events::Event, Reader};
+use regex::Regex;
+
+/// Java/Android格式化占位符，如 %s %1$s %,d %.2f %tY
+static PLACEHOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"%(?:(\d+)\$)?[-#+0,(<]*\d*(?:\.\d+)?([tT]?[a-zA-Z%])").unwrap());
+
+/// 合法的资源引用，如 @string/app_name ?attr/colorPrimary @android:string/ok
+static REFERENCE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[@?](\+?[\w.]+:)?[\w]+/[\w.]+$|^@null$").unwrap());
+
+/// 字符串中的标记标签，如 <b> </b> <a href="..."> <xliff:g id="count"> <br/>
+static MARKUP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<(/?)([A-Za-z][\w:.-]*)(?:\s+[^<>]*?)?\s*(/?)>"#).unwrap());
+
+/// 不需要结束标签的HTML标签
+const VOID_TAGS: [&str; 3] = ["br", "hr", "img"];
+
+/// 合法的资源名称，字母或下划线开头，只包含字母、数字、下划线和点
+static KEY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_.]*$").unwrap());
+
+/// Java关键字，不能作为R.string的字段名
+const JAVA_KEYWORDS: [&str; 53] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+];
+
+/// 标签名称检查策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyPolicy {
+    Off,      // 不检查
+    Warn,     // 只输出警告
+    Error,    // 存在问题时中止
+    Skip,     // 跳过不合法的标签
+    Sanitize, // 转换为snake_case
+}
+
+impl KeyPolicy {
+    /// 配置中可以使用的值
+    pub const VALUES: [&'static str; 5] = ["off", "warn", "error", "skip", "sanitize"];
+
+    /// 解析配置的值，配置解析时已检查过可选值
+    pub fn parse(value: &str) -> KeyPolicy {
+        match value {
+            "warn" => KeyPolicy::Warn,
+            "error" => KeyPolicy::Error,
+            "skip" => KeyPolicy::Skip,
+            "sanitize" => KeyPolicy::Sanitize,
+            _ => KeyPolicy::Off,
+        }
+    }
+}
+
+/// 检查标签名称能否作为Android资源名称，不合法时返回说明
+pub fn check_key(tag: &str) -> Option<&'static str> {
+    if !KEY_REGEX.is_match(tag) {
+        if tag.starts_with(|c: char| c.is_ascii_digit()) {
+            return Some("不能以数字开头");
+        }
+        return Some("只能包含字母、数字、下划线和点");
+    }
+    if JAVA_KEYWORDS.contains(&tag) {
+        return Some("不能使用Java关键字");
+    }
+    None
+}
+
+/// 将标签名称转换为snake_case，无法转换时返回None
+pub fn sanitize_key(tag: &str) -> Option<String> {
+    let mut key = String::with_capacity(tag.len());
+    let mut prev_lower = false;
+    for c in tag.chars() {
+        if c.is_ascii_uppercase() {
+            // 驼峰命名拆分为下划线
+            if prev_lower {
+                key.push('_');
+            }
+            key.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            key.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            key.push('_');
+            prev_lower = false;
+        }
+    }
+    // 合并连续的下划线，并去除首尾的下划线
+    let key = key
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if key.is_empty() {
+        return None;
+    }
+    let mut key = if key.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", key)
+    } else {
+        key
+    };
+    if JAVA_KEYWORDS.contains(&key.as_str()) {
+        key.push('_');
+    }
+    Some(key)
+}
+
+/// 检查策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Policy {
+    Off,   // 不检查
+    Warn,  // 只输出警告
+    Error, // 存在问题时中止写入
+}
+
+impl Policy {
+    /// 配置中可以使用的值
+    pub const VALUES: [&'static str; 3] = ["off", "warn", "error"];
+
+    /// 解析配置的值，配置解析时已检查过可选值
+    pub fn parse(value: &str) -> Policy {
+        match value {
+            "warn" => Policy::Warn,
+            "error" => Policy::Error,
+            _ => Policy::Off,
+        }
+    }
+}
+
+/// 检查发现的问题
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub tag: String,
+    pub lang: String,
+    pub message: String,
+}
+
+/// 是否有检查配置为error，此时写入前需要先检查全部语言
+pub fn has_error_policy(parsed_cfg: &ParsedCfg) -> bool {
+    [
+        &parsed_cfg.placeholder_check,
+        &parsed_cfg.aapt_check,
+        &parsed_cfg.markup_check,
+        &parsed_cfg.length_check,
+    ]
+    .iter()
+    .any(|policy| Policy::parse(policy) == Policy::Error)
+}
+
+/**
+ * 写入单个语言前执行检查，按配置的策略输出警告或中止
+ * @param parsed_cfg 解析后的配置
+ * @param entries 当前语言的数据
+ */
+pub fn check_lang(parsed_cfg: &ParsedCfg, entries: &LangEntries) -> Result<(), Box<dyn Error>> {
+    let mut errors = Vec::new();
+
+    let policy = Policy::parse(&parsed_cfg.placeholder_check);
+    if policy != Policy::Off && entries.lang != parsed_cfg.default_lang {
+        let issues = check_placeholders(
+            entries.lang,
+            entries.tag_value_map,
+            entries.default_value_map,
+            entries.meta,
+        );
+        report_issues("占位符", &issues, policy, parsed_cfg, entries, &mut errors);
+    }
+
+    let policy = Policy::parse(&parsed_cfg.aapt_check);
+    if policy != Policy::Off && matches!(parsed_cfg.target.as_str(), "" | "android") {
+        let issues = check_aapt(parsed_cfg, entries);
+        report_issues("aapt2", &issues, policy, parsed_cfg, entries, &mut errors);
+    }
+
+    let policy = Policy::parse(&parsed_cfg.markup_check);
+    if policy != Policy::Off {
+        let issues = check_markup(
+            entries.lang,
+            entries.tag_value_map,
+            entries.default_value_map,
+            entries.lang == parsed_cfg.default_lang,
+        );
+        report_issues("标签", &issues, policy, parsed_cfg, entries, &mut errors);
+    }
+
+    let policy = Policy::parse(&parsed_cfg.length_check);
+    if policy != Policy::Off {
+        let issues = check_length(parsed_cfg, entries);
+        report_issues("长度", &issues, policy, parsed_cfg, entries, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; ").into())
+    }
+}
+
+/**
+ * 检查Excel中所有语言，不写入文件
+ * @param cfg_json 配置JSON
+ * @param excel_path Excel文件路径
+ */
+pub fn validate(cfg_json: &str, excel_path: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    let mut source = source::open_source(excel_path, &parsed_cfg)?;
+    let lang_maps = read_excel::read_lang_maps(source.as_mut(), &parsed_cfg)?;
+    let meta = read_excel::read_sheet_meta(source.as_mut(), &parsed_cfg)?;
+    let empty_map = HashMap::new();
+    let default_value_map = lang_maps
+        .iter()
+        .find(|(lang, _)| *lang == parsed_cfg.default_lang)
+        .map(|(_, map)| map)
+        .unwrap_or(&empty_map);
+
+    let mut result = Ok(());
+    for (lang, tag_value_map) in &lang_maps {
+        // 与写入时一致，非默认语言不检查不需要翻译的标签
+        let translated_map: HashMap<String, String>;
+        let tag_value_map = if *lang == parsed_cfg.default_lang {
+            tag_value_map
+        } else {
+            translated_map = tag_value_map
+                .iter()
+                .filter(|(tag, _)| meta.translatable_map.get(*tag) != Some(&false))
+                .map(|(tag, value)| (tag.clone(), value.clone()))
+                .collect();
+            &translated_map
+        };
+        let entries = LangEntries {
+            lang,
+            tag_value_map,
+            default_value_map,
+            meta: &meta,
+        };
+        // 继续检查其他语言，最后返回第一个错误
+        if let Err(e) = check_lang(&parsed_cfg, &entries) {
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+    }
+    result
+}
+
+/// 输出问题及其在表格中的位置，策略为Error且存在问题时记录错误
+fn report_issues(
+    kind: &str,
+    issues: &[Issue],
+    policy: Policy,
+    parsed_cfg: &ParsedCfg,
+    entries: &LangEntries,
+    errors: &mut Vec<String>,
+) {
+    for issue in issues {
+        match cell_location(parsed_cfg, entries, &issue.tag, &issue.lang) {
+            Some(location) => println!(
+                "[{}] {} {} ({}): {}",
+                kind, issue.tag, issue.lang, location, issue.message
+            ),
+            None => println!("[{}] {} {}: {}", kind, issue.tag, issue.lang, issue.message),
+        }
+    }
+    if policy == Policy::Error && !issues.is_empty() {
+        errors.push(format!(
+            "{} {}检查未通过，共{}处问题",
+            entries.lang,
+            kind,
+            issues.len()
+        ));
+    }
+}
+
+/// 获取标签和语言在表格中的单元格位置，如 C12
+fn cell_location(
+    parsed_cfg: &ParsedCfg,
+    entries: &LangEntries,
+    tag: &str,
+    lang: &str,
+) -> Option<String> {
+    let row = entries.meta.row_map.get(tag)?;
+    let col = parsed_cfg
+        .lang_index_map
+        .iter()
+        .find(|(l, _)| l == lang)
+        .map(|(_, index)| *index)?;
+    Some(format!("{}{}", write_xlsx::column_name(col), row + 1))
+}
+
+/**
+ * 检查译文与默认语言的占位符是否一致，标签按字母顺序
+ * 译文或默认语言为空、或标记为非格式化字符串时跳过
+ * @param lang 当前语言
+ * @param tag_value_map 当前语言 标签 - 值
+ * @param default_value_map 默认语言 标签 - 值
+ * @param meta 标签信息
+ * @return 发现的问题
+ */
+pub fn check_placeholders(
+    lang: &str,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    meta: &SheetMeta,
+) -> Vec<Issue> {
+    let mut tags: Vec<&String> = tag_value_map.keys().collect();
+    tags.sort();
+    let mut issues = Vec::new();
+    for tag in tags {
+        // formatted="false" 的字符串中 % 不是占位符
+        if meta.formatted_map.get(tag) == Some(&false) {
+            continue;
+        }
+        let value = &tag_value_map[tag];
+        let default_value = match default_value_map.get(tag) {
+            Some(default_value) => default_value,
+            None => continue,
+        };
+        if value.trim().is_empty() || default_value.trim().is_empty() {
+            continue;
+        }
+        if let Some(message) = compare_placeholders(default_value, value) {
+            issues.push(Issue {
+                tag: tag.clone(),
+                lang: lang.to_string(),
+                message,
+            });
+        }
+    }
+    issues
+}
+
+/// 比较占位符，返回不一致的说明
+fn compare_placeholders(default_value: &str, value: &str) -> Option<String> {
+    let expected = placeholders(default_value);
+    let actual = placeholders(value);
+    if expected == actual {
+        return None;
+    }
+    let mut problems = Vec::new();
+    for (index, conversion) in &expected {
+        match actual.get(index) {
+            None => problems.push(format!("缺少第{}个参数 %{}", index, conversion)),
+            Some(c) if c != conversion => problems.push(format!(
+                "第{}个参数类型不一致 %{} -> %{}",
+                index, conversion, c
+            )),
+            _ => {}
+        }
+    }
+    for (index, conversion) in &actual {
+        if !expected.contains_key(index) {
+            problems.push(format!("多出第{}个参数 %{}", index, conversion));
+        }
+    }
+    Some(problems.join("; "))
+}
+
+/**
+ * 提取占位符，返回 参数序号 - 类型
+ * 没有指定序号的占位符按出现顺序依次编号，%% 和 %n 不是参数
+ */
+pub fn placeholders(value: &str) -> BTreeMap<u32, String> {
+    let mut result = BTreeMap::new();
+    let mut next_index = 1;
+    for caps in PLACEHOLDER_REGEX.captures_iter(value) {
+        let conversion = caps[2].to_lowercase();
+        if conversion == "%" || conversion == "n" {
+            continue;
+        }
+        let index = match caps.get(1).and_then(|m| m.as_str().parse().ok()) {
+            Some(index) => index,
+            None => {
+                let index = next_index;
+                next_index += 1;
+                index
+            }
+        };
+        result.insert(index, conversion);
+    }
+    result
+}
+
+/**
+ * 检查译文长度是否超过上限，标签按字母顺序，长度按字符数计算
+ * 表格中配置了最大长度的标签按最大长度检查，否则按配置的倍数乘以默认语言的长度检查
+ * @param parsed_cfg 解析后的配置
+ * @param entries 当前语言的数据
+ * @return 发现的问题
+ */
+pub fn check_length(parsed_cfg: &ParsedCfg, entries: &LangEntries) -> Vec<Issue> {
+    let is_default = entries.lang == parsed_cfg.default_lang;
+    let ratio = parsed_cfg
+        .max_length_ratio
+        .iter()
+        .find(|(lang, _)| lang == entries.lang)
+        .or_else(|| {
+            parsed_cfg
+                .max_length_ratio
+                .iter()
+                .find(|(lang, _)| lang == "*")
+        })
+        .map(|(_, ratio)| *ratio)
+        .filter(|_| !is_default);
+
+    let mut tags: Vec<&String> = entries.tag_value_map.keys().collect();
+    tags.sort();
+    let mut issues = Vec::new();
+    for tag in tags {
+        let value = &entries.tag_value_map[tag];
+        if value.trim().is_empty() {
+            continue;
+        }
+        let length = value.chars().count();
+        let message = match (entries.meta.max_length_map.get(tag), ratio) {
+            (Some(max_length), _) if length > *max_length => {
+                format!("长度{}超过最大长度{}", length, max_length)
+            }
+            (None, Some(ratio)) => {
+                let default_length = match entries.default_value_map.get(tag) {
+                    Some(default_value) if !default_value.trim().is_empty() => {
+                        default_value.chars().count()
+                    }
+                    _ => continue,
+                };
+                let max_length = (default_length as f64 * ratio).ceil() as usize;
+                if length <= max_length {
+                    continue;
+                }
+                format!(
+                    "长度{}超过默认语言长度{}的{}倍",
+                    length, default_length, ratio
+                )
+            }
+            _ => continue,
+        };
+        issues.push(Issue {
+            tag: tag.clone(),
+            lang: entries.lang.to_string(),
+            message,
+        });
+    }
+    issues
+}
+
+/**
+ * 按aapt2的规则检查写入strings.xml的最终内容，标签按字母顺序
+ * 为空的值不会写入，跳过
+ * @param parsed_cfg 解析后的配置
+ * @param entries 当前语言的数据
+ * @return 发现的问题
+ */
+pub fn check_aapt(parsed_cfg: &ParsedCfg, entries: &LangEntries) -> Vec<Issue> {
+    let regex = write_xml::build_regex(&parsed_cfg.regex);
+    let mut tags: Vec<&String> = entries.tag_value_map.keys().collect();
+    tags.sort();
+    let mut issues = Vec::new();
+    for tag in tags {
+        let write_value = write_xml::get_write_value(
+            tag,
+            &entries.tag_value_map[tag],
+            entries.default_value_map,
+            parsed_cfg.replace_blank_with_default,
+        );
+        if write_xml::is_blank(write_value) {
+            continue;
+        }
+        let value = write_xml::clean_value(write_value, &regex);
+        let escaped_value =
+            write_xml::escape_text(parsed_cfg.disable_escape, &value, &parsed_cfg.escape_only);
+        if let Some(message) = check_android_value(&escaped_value) {
+            issues.push(Issue {
+                tag: tag.clone(),
+                lang: entries.lang.to_string(),
+                message,
+            });
+        }
+    }
+    issues
+}
+
+/**
+ * 检查string的内容是否能通过aapt2编译
+ * @param escaped_value 写入strings.xml的内容，已转义
+ * @return 不符合规则时返回说明
+ */
+pub fn check_android_value(escaped_value: &str) -> Option<String> {
+    // 先按XML解析，得到aapt2看到的文本
+    let xml = format!("<string>{}</string>", escaped_value);
+    let mut xml_reader = Reader::from_str(&xml);
+    let mut text = String::new();
+    loop {
+        match xml_reader.read_event() {
+            Ok(Event::Text(e)) => match e.unescape() {
+                Ok(t) => text.push_str(&t),
+                Err(e) => return Some(format!("XML实体错误: {}", e)),
+            },
+            Ok(Event::CData(e)) => text.push_str(&String::from_utf8_lossy(&e)),
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => return Some(format!("XML格式错误: {}", e)),
+        }
+    }
+
+    let trimmed = text.trim_start();
+    if (trimmed.starts_with('@') || trimmed.starts_with('?')) && !REFERENCE_REGEX.is_match(trimmed)
+    {
+        return Some("以 @ 或 ? 开头会被当作资源引用，需要转义为 \\@ 或 \\?".to_string());
+    }
+
+    let mut in_quotes = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                None => return Some("结尾的反斜杠没有转义".to_string()),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    if hex.len() != 4 || !hex.chars().all(|h| h.is_ascii_hexdigit()) {
+                        return Some(format!("无效的Unicode转义 \\u{}", hex));
+                    }
+                }
+                _ => {}
+            },
+            '"' => in_quotes = !in_quotes,
+            '\'' if !in_quotes => return Some("单引号没有转义，需要写成 \\'".to_string()),
+            _ => {}
+        }
+    }
+    if in_quotes {
+        return Some("双引号不成对，需要转义为 \\\"".to_string());
+    }
+    None
+}
+
+/**
+ * 检查表格中的值的标记标签是否成对，且与默认语言使用的标签一致，标签按字母顺序
+ * 为空的值跳过
+ * @param lang 当前语言
+ * @param tag_value_map 当前语言 标签 - 值
+ * @param default_value_map 默认语言 标签 - 值
+ * @param is_default 当前语言是否为默认语言，默认语言只检查是否成对
+ * @return 发现的问题
+ */
+pub fn check_markup(
+    lang: &str,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    is_default: bool,
+) -> Vec<Issue> {
+    let mut tags: Vec<&String> = tag_value_map.keys().collect();
+    tags.sort();
+    let mut issues = Vec::new();
+    for tag in tags {
+        let value = &tag_value_map[tag];
+        if value.trim().is_empty() {
+            continue;
+        }
+        let message = match markup_tags(value) {
+            Err(message) => Some(message),
+            Ok(_) if is_default => None,
+            Ok(names) => default_value_map
+                .get(tag)
+                .filter(|default_value| !default_value.trim().is_empty())
+                .and_then(|default_value| markup_tags(default_value).ok())
+                .filter(|expected| *expected != names)
+                .map(|expected| format!("标签与默认语言不一致 {:?} -> {:?}", expected, names)),
+        };
+        if let Some(message) = message {
+            issues.push(Issue {
+                tag: tag.clone(),
+                lang: lang.to_string(),
+                message,
+            });
+        }
+    }
+    issues
+}
+
+/**
+ * 解析标记标签，检查开始标签和结束标签是否成对
+ * @return 成对时返回排序后的开始标签名称，否则返回问题说明
+ */
+pub fn markup_tags(value: &str) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    for caps in MARKUP_REGEX.captures_iter(value) {
+        let name = caps[2].to_lowercase();
+        let is_end = !caps[1].is_empty();
+        let is_self_closing = !caps[3].is_empty();
+        if is_end {
+            match stack.pop() {
+                Some(open) if open == name => {}
+                Some(open) => return Err(format!("</{}> 与 <{}> 不匹配", name, open)),
+                None => return Err(format!("多余的结束标签 </{}>", name)),
+            }
+        } else {
+            names.push(name.clone());
+            if !is_self_closing && !VOID_TAGS.contains(&name.as_str()) {
+                stack.push(name);
+            }
+        }
+    }
+    if let Some(open) = stack.pop() {
+        return Err(format!("<{}> 没有结束标签", open));
+    }
+    names.sort();
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_excel, test_util};
+
+    #[test]
+    fn test_placeholders() {
+        let map = placeholders("%1$s has %2$,d items, 100%% done%n");
+        assert_eq!(map[&1], "s");
+        assert_eq!(map[&2], "d");
+        assert_eq!(map.len(), 2);
+        assert_eq!(placeholders("%s and %d")[&2], "d");
+
+        // 调整位置参数的顺序是允许的
+        assert_eq!(compare_placeholders("%1$s %2$d", "%2$d %1$s"), None);
+        assert_eq!(compare_placeholders("%S", "%s"), None);
+        assert!(compare_placeholders("%1$s %2$d", "%1$s").is_some());
+        assert!(compare_placeholders("%s %d", "%d %s").is_some());
+        assert!(compare_placeholders("Hello", "Hi %s").is_some());
+    }
+
+    #[test]
+    fn test_check_placeholders() {
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","fr":"French"},"escapeOnly":{},"formattedName":"Formatted"}"#;
+        let csv = "tag,English,French,Formatted\ncount,%d items,%s éléments,\npercent,50%off,-50 %,false\n";
+        let (parsed_cfg, mut source) = test_util::csv_source(cfg, csv);
+        let meta = read_excel::read_sheet_meta(&mut source, &parsed_cfg).unwrap();
+        let lang_maps: HashMap<String, HashMap<String, String>> =
+            read_excel::read_lang_maps(&mut source, &parsed_cfg)
+                .unwrap()
+                .into_iter()
+                .collect();
+        // 非格式化字符串percent不检查
+        let tags: Vec<String> = check_placeholders("fr", &lang_maps["fr"], &lang_maps["en"], &meta)
+            .into_iter()
+            .map(|issue| issue.tag)
+            .collect();
+        assert_eq!(tags, vec!["count"]);
+    }
+
+    #[test]
+    fn test_check_length() {
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","de":"German"},"escapeOnly":{},"maxLengthName":"Max","maxLengthRatio":{"*":1.5}}"#;
+        let csv = "tag,English,German,Max\nok,OK,Okay!!,5\nsave,Save,Speichern,\nyes,Yes,Jawohl,\n";
+        let (parsed_cfg, mut source) = test_util::csv_source(cfg, csv);
+        let meta = read_excel::read_sheet_meta(&mut source, &parsed_cfg).unwrap();
+        let lang_maps: HashMap<String, HashMap<String, String>> =
+            read_excel::read_lang_maps(&mut source, &parsed_cfg)
+                .unwrap()
+                .into_iter()
+                .collect();
+        let entries = LangEntries {
+            lang: "de",
+            tag_value_map: &lang_maps["de"],
+            default_value_map: &lang_maps["en"],
+            meta: &meta,
+        };
+        let tags: Vec<String> = check_length(&parsed_cfg, &entries)
+            .into_iter()
+            .map(|issue| issue.tag)
+            .collect();
+        assert_eq!(tags, vec!["ok", "save", "yes"]);
+
+        // 默认语言只按最大长度检查
+        let entries = LangEntries {
+            lang: "en",
+            tag_value_map: &lang_maps["en"],
+            ..entries
+        };
+        assert!(check_length(&parsed_cfg, &entries).is_empty());
+    }
+
+    #[test]
+    fn test_check_android_value() {
+        assert_eq!(check_android_value("Don\\'t &amp; &lt;b&gt;"), None);
+        assert_eq!(check_android_value("\"Don't\""), None);
+        assert_eq!(check_android_value("@string/app_name"), None);
+        assert_eq!(check_android_value("\\@home"), None);
+        assert_eq!(check_android_value("<b>bold</b> \\u00A0"), None);
+        assert!(check_android_value("Don&apos;t").is_some());
+        assert!(check_android_value("@home").is_some());
+        assert!(check_android_value("? what").is_some());
+        assert!(check_android_value("say \"hi").is_some());
+        assert!(check_android_value("end\\").is_some());
+        assert!(check_android_value("\\u12").is_some());
+        assert!(check_android_value("a & b").is_some());
+    }
+
+    #[test]
+    fn test_key() {
+        assert_eq!(check_key("app_name"), None);
+        assert_eq!(check_key("menu.title"), None);
+        assert!(check_key("app name").is_some());
+        assert!(check_key("app-name").is_some());
+        assert!(check_key("404_title").is_some());
+        assert!(check_key("class").is_some());
+
+        assert_eq!(sanitize_key("App Name").unwrap(), "app_name");
+        assert_eq!(
+            sanitize_key("loginButton-title").unwrap(),
+            "login_button_title"
+        );
+        assert_eq!(sanitize_key("404 page").unwrap(), "_404_page");
+        assert_eq!(sanitize_key("new").unwrap(), "new_");
+        assert_eq!(sanitize_key("--"), None);
+    }
+
+    #[test]
+    fn test_markup_tags() {
+        assert_eq!(
+            markup_tags(r#"<b>Hi</b> <a href="x">link</a><br/> 1 < 2"#).unwrap(),
+            vec!["a", "b", "br"]
+        );
+        assert_eq!(
+            markup_tags(r#"<xliff:g id="n">%d</xliff:g> items"#).unwrap(),
+            vec!["xliff:g"]
+        );
+        assert!(markup_tags("<b>Hi</i>").is_err());
+        assert!(markup_tags("<b>Hi").is_err());
+        assert!(markup_tags("Hi</b>").is_err());
+
+        let default_map = HashMap::from([("k".to_string(), "<b>Save</b> now".to_string())]);
+        let map = HashMap::from([("k".to_string(), "<i>保存</i>".to_string())]);
+        assert_eq!(check_markup("zh", &map, &default_map, false).len(), 1);
+        assert_eq!(
+            check_markup("en", &default_map, &default_map, true).len(),
+            0
+        );
+    }
+}
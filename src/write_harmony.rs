@@ -0,0 +1,311 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs::{self, create_dir_all},
+    path::Path,
+};
+
+use crate::{
+    config::{self, ParsedCfg},
+    find_files, read_excel,
+    target::{self, Entries, LangEntries, LocalizationTarget},
+    write_xml,
+};
+use serde_json::{json, Map, Value};
+
+const JSON_STRING: &str = "string";
+const JSON_NAME: &str = "name";
+const JSON_VALUE: &str = "value";
+// 鸿蒙资源目录
+const RESOURCES_DIR: &str = "resources";
+const ELEMENT_DIR: &str = "element";
+const STRING_JSON: &str = "string.json";
+const BASE_LOCALE: &str = "base";
+const ENTRY_MODULE: &str = "entry";
+
+/// 鸿蒙 resources/<locale>/element/string.json 输出目标
+pub struct HarmonyTarget;
+
+impl LocalizationTarget for HarmonyTarget {
+    /// 默认语言对应base，其他语言对应 语言_地区 目录，缺失的文件会在写入时新建
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        let ignore_folders: Vec<&str> = parsed_cfg
+            .ignore_folder
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let resources_folder = match find_resources_folder(dir, &ignore_folders) {
+            Some(folder) => folder,
+            None => {
+                println!("未找到resources文件夹");
+                return Err("未找到resources文件夹".into());
+            }
+        };
+        println!("找到resources文件夹: {}", resources_folder);
+        let paths: HashMap<String, String> =
+            find_files::collect_nested_target_files(&resources_folder, ELEMENT_DIR, STRING_JSON)
+                .into_iter()
+                .collect();
+
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .map(|(lang, _)| {
+                let locale = harmony_locale(lang, &parsed_cfg.default_lang);
+                let path = match paths.get(&locale) {
+                    Some(path) => path.to_string(),
+                    None => Path::new(&resources_folder)
+                        .join(&locale)
+                        .join(ELEMENT_DIR)
+                        .join(STRING_JSON)
+                        .to_string_lossy()
+                        .to_string(),
+                };
+                (lang.clone(), path)
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        let root = read_string_json(path)?;
+        Ok(root
+            .get(JSON_STRING)
+            .and_then(Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let name = entry.get(JSON_NAME).and_then(Value::as_str)?;
+                        let value = entry.get(JSON_VALUE).and_then(Value::as_str).unwrap_or("");
+                        Some((name.to_string(), value.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            if !parent.is_dir() {
+                create_dir_all(parent)?;
+                println!("新建文件: {}", path);
+            }
+        }
+        update_string_json(
+            path,
+            entries.tag_value_map,
+            entries.default_value_map,
+            parsed_cfg,
+        )
+    }
+}
+
+/// 更新鸿蒙string.json
+pub fn update(cfg_json: &str, excel_path: &str, project_dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    target::quick_update_target(&HarmonyTarget, &parsed_cfg, excel_path, project_dir)
+}
+
+/**
+ * 查找模块的 src/main/resources 文件夹，AppScope/resources 等其他resources不会使用
+ * 有多个模块时优先使用entry模块，其次使用按路径排序的第一个
+ * @param dir 工程、模块或resources文件夹路径
+ * @param ignore 忽略的文件夹
+ */
+fn find_resources_folder(dir: &str, ignore: &[&str]) -> Option<String> {
+    if Path::new(dir).file_name().unwrap_or_default() == RESOURCES_DIR {
+        return Some(dir.to_string());
+    }
+    let module_resources = Path::new("src").join("main").join(RESOURCES_DIR);
+    let folders: Vec<String> = find_files::collect_target_folders(dir, RESOURCES_DIR, ignore)
+        .into_iter()
+        .filter(|folder| Path::new(folder).ends_with(&module_resources))
+        .collect();
+    let entry_resources = Path::new(ENTRY_MODULE).join(&module_resources);
+    let folder = folders
+        .iter()
+        .find(|folder| Path::new(folder).ends_with(&entry_resources))
+        .or(folders.first())?;
+    if folders.len() > 1 {
+        println!(
+            "找到{}个模块的resources文件夹，使用: {}",
+            folders.len(),
+            folder
+        );
+    }
+    Some(folder.clone())
+}
+
+/// 语言对应的鸿蒙限定词目录，默认语言为base
+fn harmony_locale(lang: &str, default_lang: &str) -> String {
+    if lang == default_lang {
+        BASE_LOCALE.to_string()
+    } else {
+        config::locale_code(lang)
+    }
+}
+
+/// 读取string.json，文件不存在或为空时返回空对象
+fn read_string_json(path: &str) -> Result<Map<String, Value>, Box<dyn Error>> {
+    if !Path::new(path).is_file() {
+        return Ok(Map::new());
+    }
+    let content = fs::read_to_string(path)?;
+    if write_xml::is_blank(&content) {
+        return Ok(Map::new());
+    }
+    let root = serde_json::from_str::<Value>(&content)?
+        .as_object()
+        .cloned()
+        .ok_or("string.json格式错误")?;
+    Ok(root)
+}
+
+/// 更新单个string.json，保留表格中不存在的条目
+fn update_string_json(
+    path: &str,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    parsed_cfg: &ParsedCfg,
+) -> Result<(), Box<dyn Error>> {
+    let regex = write_xml::build_regex(&parsed_cfg.regex);
+    let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
+
+    let mut root = read_string_json(path)?;
+
+    let mut entries: Vec<Value> = if parsed_cfg.reset {
+        Vec::new()
+    } else {
+        root.get(JSON_STRING)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let mut updated_tags = HashSet::new();
+    for entry in entries.iter_mut() {
+        let name = match entry.get(JSON_NAME).and_then(Value::as_str) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        let value = match tag_value_map.get(&name) {
+            Some(value) => value,
+            None => continue,
+        };
+        let write_value =
+            write_xml::get_write_value(&name, value, default_value_map, replace_blank_with_default);
+        let old_value = entry.get(JSON_VALUE).and_then(Value::as_str).unwrap_or("");
+        if !(write_xml::is_blank(write_value) && write_xml::is_blank(old_value)) {
+            entry[JSON_VALUE] = Value::String(write_xml::clean_value(write_value, &regex));
+        }
+        updated_tags.insert(name);
+    }
+
+    // 追加缺失的条目
+    let mut missing_tags: Vec<&String> = tag_value_map
+        .keys()
+        .filter(|tag| !updated_tags.contains(*tag))
+        .collect();
+    missing_tags.sort();
+    for tag in missing_tags {
+        let write_value = write_xml::get_write_value(
+            tag,
+            &tag_value_map[tag],
+            default_value_map,
+            replace_blank_with_default,
+        );
+        entries.push(json!({
+            JSON_NAME: tag,
+            JSON_VALUE: write_xml::clean_value(write_value, &regex),
+        }));
+    }
+
+    root.insert(JSON_STRING.to_string(), Value::Array(entries));
+    let mut content = serde_json::to_string_pretty(&Value::Object(root))?;
+    content.push('\n');
+    write_xml::write_file_atomic(Path::new(path), &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target::SheetMeta;
+
+    fn entries(data: &[(&str, &str)]) -> Entries {
+        data.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_string_json_round_trip() {
+        let dir = std::env::temp_dir().join("excel_to_xml_test_harmony");
+        fs::remove_dir_all(&dir).ok();
+        let base = dir.join("entry/src/main/resources/base/element");
+        create_dir_all(&base).unwrap();
+        // 其他resources文件夹不会使用
+        create_dir_all(dir.join("AppScope/resources/base/element")).unwrap();
+        create_dir_all(dir.join("feature/src/main/resources/base/element")).unwrap();
+        fs::write(
+            base.join(STRING_JSON),
+            r#"{"string":[{"name":"keep","value":"Keep"},{"name":"hello","value":"Old"}]}"#,
+        )
+        .unwrap();
+
+        let cfg = r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English","zh-rTW":"Chinese"},"escapeOnly":{},"replaceBlankWithDefault":true}"#;
+        let parsed_cfg = ParsedCfg::from_json(cfg).unwrap();
+        let files: HashMap<String, String> = HarmonyTarget
+            .discover(dir.to_str().unwrap(), &parsed_cfg)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert!(files["en"].ends_with("entry/src/main/resources/base/element/string.json"));
+        assert!(files["zh-rTW"].ends_with("resources/zh_TW/element/string.json"));
+
+        let meta = SheetMeta::default();
+        let default_value_map: HashMap<String, String> =
+            entries(&[("hello", "Hello \"world\""), ("bye", "Bye")])
+                .into_iter()
+                .collect();
+        let en = LangEntries {
+            lang: "en",
+            tag_value_map: &default_value_map,
+            default_value_map: &HashMap::new(),
+            meta: &meta,
+        };
+        HarmonyTarget.apply(&files["en"], &en, &parsed_cfg).unwrap();
+        // 保留已有条目的顺序，缺失的条目追加在末尾
+        assert_eq!(
+            HarmonyTarget.read_entries(&files["en"]).unwrap(),
+            entries(&[
+                ("keep", "Keep"),
+                ("hello", "Hello \"world\""),
+                ("bye", "Bye")
+            ])
+        );
+
+        // 文件不存在时新建，空值使用默认语言替换
+        let tag_value_map: HashMap<String, String> = entries(&[("hello", "你好"), ("bye", " ")])
+            .into_iter()
+            .collect();
+        let zh = LangEntries {
+            lang: "zh-rTW",
+            tag_value_map: &tag_value_map,
+            default_value_map: &default_value_map,
+            meta: &meta,
+        };
+        HarmonyTarget
+            .apply(&files["zh-rTW"], &zh, &parsed_cfg)
+            .unwrap();
+        assert_eq!(
+            HarmonyTarget.read_entries(&files["zh-rTW"]).unwrap(),
+            entries(&[("bye", "Bye"), ("hello", "你好")])
+        );
+        fs::remove_dir_all(&dir).ok();
+    }
+}
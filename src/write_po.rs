@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, create_dir_all, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    config::{self, ParsedCfg},
+    read_excel, read_po,
+    target::{self, Entries, LangEntries, LocalizationTarget},
+    write_xml,
+};
+use regex::Regex;
+
+/// gettext PO文件输出目标，每种语言对应 <locale>.po
+/// 标签写入msgctxt，默认语言写入msgid，当前语言写入msgstr
+pub struct PoTarget;
+
+impl LocalizationTarget for PoTarget {
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .map(|(lang, _)| {
+                let path = Path::new(dir).join(format!("{}.po", config::locale_code(lang)));
+                (lang.clone(), path.to_string_lossy().to_string())
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Ok(read_po::parse_po(&content).into_iter().collect())
+    }
+
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let regex = write_xml::build_regex(&parsed_cfg.regex);
+        write_po_file(
+            path,
+            entries.lang,
+            entries.tag_value_map,
+            entries.default_value_map,
+            &entries.meta.comment_map,
+            &regex,
+        )?;
+        println!("写入PO文件: {}", path.display());
+        Ok(())
+    }
+}
+
+/**
+ * 导出gettext PO文件
+ * @param cfg_json 配置JSON
+ * @param excel_path Excel文件路径
+ * @param out_dir 输出文件夹
+ */
+pub fn export(cfg_json: &str, excel_path: &str, out_dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    target::quick_update_target(&PoTarget, &parsed_cfg, excel_path, out_dir)
+}
+
+/// 写入单个语言的PO文件
+fn write_po_file(
+    path: &Path,
+    lang: &str,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    comment_map: &HashMap<String, String>,
+    regex: &Option<Regex>,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    // 文件头
+    writeln!(writer, "msgid \"\"")?;
+    writeln!(writer, "msgstr \"\"")?;
+    writeln!(writer, "\"Language: {}\\n\"", config::locale_code(lang))?;
+    writeln!(writer, "\"MIME-Version: 1.0\\n\"")?;
+    writeln!(writer, "\"Content-Type: text/plain; charset=UTF-8\\n\"")?;
+    writeln!(writer, "\"Content-Transfer-Encoding: 8bit\\n\"")?;
+
+    // 按标签排序，保证多次导出结果一致
+    let mut tags: Vec<&String> = tag_value_map.keys().collect();
+    tags.sort();
+    for tag in tags {
+        let value = write_xml::clean_value(&tag_value_map[tag], regex);
+        // 默认语言为空时，使用标签作为msgid
+        let source = default_value_map
+            .get(tag)
+            .map(|v| write_xml::clean_value(v, regex))
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| tag.to_string());
+
+        writeln!(writer)?;
+        // 备注写入提取注释，每行一条
+        if let Some(comment) = comment_map.get(tag) {
+            for line in comment.trim().lines() {
+                writeln!(writer, "#. {}", line.trim_end())?;
+            }
+        }
+        write_po_string(&mut writer, "msgctxt", tag)?;
+        write_po_string(&mut writer, "msgid", &source)?;
+        write_po_string(&mut writer, "msgstr", &value)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// 写入PO字符串，多行文本按换行拆分为多段
+fn write_po_string(writer: &mut impl Write, keyword: &str, value: &str) -> io::Result<()> {
+    let lines: Vec<&str> = value.split_inclusive('\n').collect();
+    if lines.len() <= 1 {
+        return writeln!(writer, "{} \"{}\"", keyword, escape_po(value));
+    }
+    writeln!(writer, "{} \"\"", keyword)?;
+    for line in lines {
+        writeln!(writer, "\"{}\"", escape_po(line))?;
+    }
+    Ok(())
+}
+
+/// 转义PO字符串中的特殊字符
+fn escape_po(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_po_string() {
+        let mut out = Vec::new();
+        write_po_string(&mut out, "msgid", "Say \"hi\" \\ bye").unwrap();
+        write_po_string(&mut out, "msgstr", "第一行\n第二行\t").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "msgid \"Say \\\"hi\\\" \\\\ bye\"\nmsgstr \"\"\n\"第一行\\n\"\n\"第二行\\t\"\n"
+        );
+        assert_eq!(escape_po("a\r\nb"), "a\\r\\nb");
+    }
+
+    #[test]
+    fn test_po_round_trip() {
+        let path = std::env::temp_dir().join("excel_to_xml_test_write.po");
+        let map = |data: &[(&str, &str)]| -> HashMap<String, String> {
+            data.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let tag_value_map = map(&[
+            ("quote", "他说 \"你好\" \\n"),
+            ("multi", "第一行\n第二行"),
+            ("blank", ""),
+        ]);
+        let default_value_map =
+            map(&[("quote", "He said \"hi\" \\n"), ("multi", "Line 1\nLine 2")]);
+        let comment_map = map(&[("multi", "Two lines\nshown together")]);
+        write_po_file(
+            &path,
+            "zh-rTW",
+            &tag_value_map,
+            &default_value_map,
+            &comment_map,
+            &None,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"Language: zh_TW\\n\""));
+        assert!(content.contains(
+            "\n#. Two lines\n#. shown together\nmsgctxt \"multi\"\nmsgid \"\"\n\"Line 1\\n\"\n\"Line 2\"\nmsgstr \"\"\n\"第一行\\n\"\n\"第二行\"\n"
+        ));
+        // 默认语言为空时使用标签作为msgid
+        assert!(content.contains("msgctxt \"blank\"\nmsgid \"blank\"\nmsgstr \"\"\n"));
+        assert_eq!(read_po::parse_po(&content), tag_value_map);
+    }
+}
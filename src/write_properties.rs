@@ -0,0 +1,408 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs::{self, create_dir_all},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{self, ParsedCfg},
+    read_excel,
+    target::{self, Entries, LangEntries, LocalizationTarget},
+    write_xml,
+};
+
+/// properties中的一条逻辑行（可能由多行续行组成）
+struct LogicalLine {
+    raw: String,    // 原始内容，保留续行
+    joined: String, // 拼接续行后的内容
+}
+
+/// Java properties资源文件输出目标
+/// 默认语言写入 <base>.properties，其他语言写入 <base>_<locale>.properties
+pub struct PropertiesTarget;
+
+impl LocalizationTarget for PropertiesTarget {
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .map(|(lang, _)| {
+                let path = properties_path(dir, parsed_cfg, lang);
+                (lang.clone(), path.to_string_lossy().to_string())
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Ok(logical_lines(&content)
+            .iter()
+            .filter_map(|line| parse_property(&line.joined))
+            .collect())
+    }
+
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        update_properties_file(
+            path,
+            entries.tag_value_map,
+            entries.default_value_map,
+            &entries.meta.comment_map,
+            parsed_cfg,
+        )?;
+        println!("写入properties文件: {}", path.display());
+        Ok(())
+    }
+}
+
+/**
+ * 导出Java properties资源文件
+ * 已存在的文件只更新表格中的键，保留注释与其他键
+ * @param cfg_json 配置JSON
+ * @param excel_path Excel文件路径
+ * @param out_dir properties所在文件夹
+ */
+pub fn export(cfg_json: &str, excel_path: &str, out_dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    target::quick_update_target(&PropertiesTarget, &parsed_cfg, excel_path, out_dir)
+}
+
+/// 获取语言对应的properties文件路径
+fn properties_path(out_dir: &str, parsed_cfg: &ParsedCfg, lang: &str) -> PathBuf {
+    let base_name = &parsed_cfg.properties_base_name;
+    let file_name = if lang == parsed_cfg.default_lang {
+        format!("{}.properties", base_name)
+    } else {
+        format!("{}_{}.properties", base_name, config::locale_code(lang))
+    };
+    Path::new(out_dir).join(file_name)
+}
+
+/// 更新properties文件，文件不存在时新建，备注写为键前面带标记的注释
+fn update_properties_file(
+    path: &Path,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    comment_map: &HashMap<String, String>,
+    parsed_cfg: &ParsedCfg,
+) -> Result<(), Box<dyn Error>> {
+    let regex = write_xml::build_regex(&parsed_cfg.regex);
+    let ascii = !parsed_cfg.properties_encoding.eq_ignore_ascii_case("utf-8");
+    let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
+
+    let existing = if path.is_file() && !parsed_cfg.reset {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+
+    let mut content = String::with_capacity(existing.len());
+    let mut updated_tags = HashSet::new();
+    // 工具写入的注释延迟写入，以便更新键时替换
+    let mut pending: Vec<String> = Vec::new();
+    for line in logical_lines(&existing) {
+        if is_marked_comment(&line.joined) {
+            pending.push(line.raw);
+            continue;
+        }
+        let entry = parse_property(&line.joined)
+            .and_then(|(key, old_value)| tag_value_map.get(&key).map(|v| (key, old_value, v)));
+        let (key, old_value, value) = match entry {
+            Some(entry) => entry,
+            None => {
+                flush_pending(&mut content, &mut pending);
+                content.push_str(&line.raw);
+                content.push('\n');
+                continue;
+            }
+        };
+        match format_comment(comment_map, &key, ascii) {
+            Some(comment) => {
+                pending.clear();
+                content.push_str(&comment);
+            }
+            None => flush_pending(&mut content, &mut pending),
+        }
+        updated_tags.insert(key.clone());
+        let write_value =
+            write_xml::get_write_value(&key, value, default_value_map, replace_blank_with_default);
+        if write_xml::is_blank(write_value) && write_xml::is_blank(&old_value) {
+            // 内容均为空，不做修改
+            content.push_str(&line.raw);
+        } else {
+            let write_value = write_xml::clean_value(write_value, &regex);
+            content.push_str(&format_property(&key, &write_value, ascii));
+        }
+        content.push('\n');
+    }
+
+    flush_pending(&mut content, &mut pending);
+
+    // 追加文件中缺失的键
+    let mut missing_tags: Vec<&String> = tag_value_map
+        .keys()
+        .filter(|tag| !updated_tags.contains(*tag))
+        .collect();
+    missing_tags.sort();
+    for tag in missing_tags {
+        let write_value = write_xml::get_write_value(
+            tag,
+            &tag_value_map[tag],
+            default_value_map,
+            replace_blank_with_default,
+        );
+        let write_value = write_xml::clean_value(write_value, &regex);
+        if let Some(comment) = format_comment(comment_map, tag, ascii) {
+            content.push_str(&comment);
+        }
+        content.push_str(&format_property(tag, &write_value, ascii));
+        content.push('\n');
+    }
+
+    write_xml::write_file_atomic(path, &content)
+}
+
+/// 写入延迟的注释行
+fn flush_pending(content: &mut String, pending: &mut Vec<String>) {
+    for line in pending.drain(..) {
+        content.push_str(&line);
+        content.push('\n');
+    }
+}
+
+/// 是否为工具写入的注释行
+fn is_marked_comment(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(['#', '!'])
+        .is_some_and(|rest| rest.trim_start().starts_with(write_xml::COMMENT_MARKER))
+}
+
+/// 生成键的注释行，每行以标记开头，备注为空时返回None
+/// * @param ascii 非ASCII字符是否转为 \uXXXX
+fn format_comment(comment_map: &HashMap<String, String>, key: &str, ascii: bool) -> Option<String> {
+    let comment = comment_map.get(key)?.trim();
+    if comment.is_empty() {
+        return None;
+    }
+    let mut lines = String::new();
+    for line in comment.lines() {
+        lines.push_str(&format!("# {} ", write_xml::COMMENT_MARKER));
+        for c in line.trim_end().chars() {
+            if ascii && !(' '..='~').contains(&c) {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    lines.push_str(&format!("\\u{:04X}", unit));
+                }
+            } else {
+                lines.push(c);
+            }
+        }
+        lines.push('\n');
+    }
+    Some(lines)
+}
+
+/// 按逻辑行拆分，以奇数个反斜杠结尾的行与下一行合并
+fn logical_lines(content: &str) -> Vec<LogicalLine> {
+    let mut lines = Vec::new();
+    let mut physical = content.lines();
+    while let Some(first) = physical.next() {
+        let mut raw = first.to_string();
+        let mut joined = String::new();
+        let mut cur = first;
+        let is_comment = matches!(first.trim_start().chars().next(), Some('#') | Some('!'));
+        while !is_comment && ends_with_continuation(cur) {
+            joined.push_str(&cur[..cur.len() - 1]);
+            match physical.next() {
+                Some(next) => {
+                    raw.push('\n');
+                    raw.push_str(next);
+                    cur = next.trim_start();
+                }
+                None => {
+                    cur = "";
+                    break;
+                }
+            }
+        }
+        joined.push_str(cur);
+        lines.push(LogicalLine { raw, joined });
+    }
+    lines
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// 解析键值对，注释与空行返回None
+fn parse_property(line: &str) -> Option<(String, String)> {
+    let line = line.trim_start();
+    if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+        return None;
+    }
+
+    // 键在第一个未转义的空白、=或:处结束
+    let mut key_end = line.len();
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if matches!(c, ' ' | '\t' | '\x0c' | '=' | ':') {
+            key_end = i;
+            break;
+        }
+    }
+    let key = unescape_property(&line[..key_end]);
+
+    let mut rest = line[key_end..].trim_start_matches([' ', '\t', '\x0c']);
+    if let Some(stripped) = rest.strip_prefix(['=', ':']) {
+        rest = stripped.trim_start_matches([' ', '\t', '\x0c']);
+    }
+    Some((key, unescape_property(rest)))
+}
+
+/// 还原properties中的转义字符，\uXXXX 按UTF-16处理以支持代理对
+fn unescape_property(value: &str) -> String {
+    let mut units: Vec<u16> = Vec::with_capacity(value.len());
+    let mut buf = [0u16; 2];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let c = if c != '\\' {
+            c
+        } else {
+            match chars.next() {
+                Some('t') => '\t',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('f') => '\x0c',
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    units.push(u16::from_str_radix(&hex, 16).unwrap_or(0xfffd));
+                    continue;
+                }
+                Some(other) => other,
+                None => continue,
+            }
+        };
+        units.extend_from_slice(c.encode_utf16(&mut buf));
+    }
+    String::from_utf16_lossy(&units)
+}
+
+/// 生成 key=value 行
+fn format_property(key: &str, value: &str, ascii: bool) -> String {
+    format!(
+        "{}={}",
+        escape_property(key, true, ascii),
+        escape_property(value, false, ascii)
+    )
+}
+
+/// 转义properties键或值
+/// * @param is_key 键需要额外转义空格、=、:、#、!
+/// * @param ascii 非ASCII字符是否转为 \uXXXX
+fn escape_property(value: &str, is_key: bool, ascii: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\x0c' => escaped.push_str("\\f"),
+            ' ' if is_key || i == 0 => escaped.push_str("\\ "),
+            '=' | ':' | '#' | '!' if is_key => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ if ascii && !(' '..='~').contains(&c) => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_round_trip() {
+        let value = " 你好 = world:\n😀\\";
+        let line = format_property("a key", value, true);
+        assert_eq!(
+            line,
+            "a\\ key=\\ \\u4F60\\u597D = world:\\n\\uD83D\\uDE00\\\\"
+        );
+        assert_eq!(
+            parse_property(&line),
+            Some(("a key".to_string(), value.to_string()))
+        );
+        let utf8_line = format_property("key", value, false);
+        assert_eq!(utf8_line, "key=\\ 你好 = world:\\n😀\\\\");
+    }
+
+    #[test]
+    fn test_logical_lines() {
+        let content = "# comment \\\nkey = first \\\n    second\nother:value";
+        let lines = logical_lines(content);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].raw, "key = first \\\n    second");
+        assert_eq!(
+            parse_property(&lines[1].joined),
+            Some(("key".to_string(), "first second".to_string()))
+        );
+        assert_eq!(
+            parse_property(&lines[2].joined),
+            Some(("other".to_string(), "value".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_comment_not_duplicated() {
+        let path = std::env::temp_dir().join("excel_to_xml_test_comment.properties");
+        fs::write(&path, "# note\nhello=Old\nkeep=Keep\n").unwrap();
+        let parsed_cfg = ParsedCfg::from_json(
+            r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English"},"escapeOnly":{}}"#,
+        )
+        .unwrap();
+        let tag_value_map: HashMap<String, String> = [("hello", "New"), ("bye", "Bye")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let comment_map: HashMap<String, String> = [("hello", "问候\nline 2"), ("bye", "Farewell")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        // 手写的注释保留，再次同步时替换带标记的注释
+        let expected = "# note\n# [excel_to_xml] \\u95EE\\u5019\n# [excel_to_xml] line 2\nhello=New\nkeep=Keep\n# [excel_to_xml] Farewell\nbye=Bye\n";
+        for _ in 0..2 {
+            update_properties_file(
+                &path,
+                &tag_value_map,
+                &HashMap::new(),
+                &comment_map,
+                &parsed_cfg,
+            )
+            .unwrap();
+            assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs::{self, create_dir_all, remove_file, rename, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{self, ParsedCfg},
+    read_excel,
+    target::{self, Entries, LangEntries, LocalizationTarget},
+    write_xml,
+};
+use quick_xml::{
+    events::{BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use regex::Regex;
+
+const RESX_B_ROOT: &[u8] = b"root";
+const RESX_DATA: &str = "data";
+const RESX_B_DATA: &[u8] = b"data";
+const RESX_VALUE: &str = "value";
+const RESX_B_VALUE: &[u8] = b"value";
+const RESX_COMMENT: &str = "comment";
+const RESX_B_COMMENT: &[u8] = b"comment";
+const RESX_NAME: &str = "name";
+const RESX_B_NAME: &[u8] = b"name";
+// 格式化相关常量
+const RESX_NEWLINE: &str = "\n";
+const RESX_INDENT: &str = "\n  ";
+const RESX_VALUE_INDENT: &str = "\n    ";
+
+/// 新建resx文件时使用的模板，包含标准resheader
+const RESX_TEMPLATE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <resheader name="resmimetype">
+    <value>text/microsoft-resx</value>
+  </resheader>
+  <resheader name="version">
+    <value>2.0</value>
+  </resheader>
+  <resheader name="reader">
+    <value>System.Resources.ResXResourceReader, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+  </resheader>
+  <resheader name="writer">
+    <value>System.Resources.ResXResourceWriter, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+  </resheader>
+</root>
+"#;
+
+/// .NET resx资源文件输出目标
+/// 默认语言写入 <base>.resx，其他语言写入 <base>.<culture>.resx
+pub struct ResxTarget;
+
+impl LocalizationTarget for ResxTarget {
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .map(|(lang, _)| {
+                let path = resx_path(dir, parsed_cfg, lang);
+                (lang.clone(), path.to_string_lossy().to_string())
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        let mut xml_reader = Reader::from_str(&content);
+        let mut entries = Vec::new();
+        let mut current_tag_name: Option<String> = None;
+        loop {
+            match xml_reader.read_event()? {
+                Event::Start(e) if e.name().as_ref() == RESX_B_DATA => {
+                    current_tag_name = data_name(&e);
+                }
+                Event::Start(e) if e.name().as_ref() == RESX_B_VALUE => {
+                    let end = e.to_end().into_owned();
+                    let text = xml_reader.read_text(end.name())?;
+                    if let Some(tag) = current_tag_name.take() {
+                        let text = quick_xml::escape::unescape(&text)?.to_string();
+                        entries.push((tag, text));
+                    }
+                }
+                Event::End(e) if e.name().as_ref() == RESX_B_DATA => {
+                    current_tag_name = None;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 文件不存在或需要重置时，先使用标准模板新建
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        if !path.is_file() || parsed_cfg.reset {
+            fs::write(path, RESX_TEMPLATE)?;
+        }
+        update_resx_file(
+            path,
+            entries.tag_value_map,
+            entries.default_value_map,
+            &entries.meta.comment_map,
+            parsed_cfg,
+        )?;
+        println!("写入resx文件: {}", path.display());
+        Ok(())
+    }
+}
+
+/**
+ * 导出.NET resx资源文件
+ * 已存在的文件保留resheader和表格中不存在的条目
+ * @param cfg_json 配置JSON
+ * @param excel_path Excel文件路径
+ * @param out_dir resx所在文件夹
+ */
+pub fn export(cfg_json: &str, excel_path: &str, out_dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    target::quick_update_target(&ResxTarget, &parsed_cfg, excel_path, out_dir)
+}
+
+/// 获取语言对应的resx文件路径
+fn resx_path(out_dir: &str, parsed_cfg: &ParsedCfg, lang: &str) -> PathBuf {
+    let base_name = &parsed_cfg.resx_base_name;
+    let file_name = if lang == parsed_cfg.default_lang {
+        format!("{}.resx", base_name)
+    } else {
+        format!("{}.{}.resx", base_name, config::language_tag(lang))
+    };
+    Path::new(out_dir).join(file_name)
+}
+
+/// 更新resx文件，只替换data中的value和comment，缺失的条目在root结束前追加
+fn update_resx_file(
+    path: &Path,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    comment_map: &HashMap<String, String>,
+    parsed_cfg: &ParsedCfg,
+) -> Result<(), Box<dyn Error>> {
+    let temp_path = path.with_extension("resx.temp");
+    let regex = write_xml::build_regex(&parsed_cfg.regex);
+    let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
+
+    let content = fs::read_to_string(path)?;
+    let mut xml_reader = Reader::from_str(&content);
+    let mut xml_writer = Writer::new(BufWriter::new(File::create(&temp_path)?));
+
+    let mut current_tag_name: Option<String> = None;
+    let mut updated_tags = HashSet::new();
+    // 当前data需要写入的备注，写入后置为None
+    let mut current_comment: Option<&str> = None;
+    // 当前data中最后的空白，没有comment时在其前面添加
+    let mut pending_whitespace: Option<BytesText> = None;
+    loop {
+        let event = xml_reader.read_event()?;
+        if let Event::Text(text) = &event {
+            if current_comment.is_some() && write_xml::is_blank(&text.unescape()?) {
+                if let Some(text) = pending_whitespace.replace(text.clone()) {
+                    xml_writer.write_event(Event::Text(text))?;
+                }
+                continue;
+            }
+        }
+        if matches!(&event, Event::End(e) if e.name().as_ref() == RESX_B_DATA) {
+            if let Some(comment) = current_comment.take() {
+                // 原来没有comment，在data结束前添加
+                xml_writer.write_event(Event::Text(BytesText::new(RESX_VALUE_INDENT)))?;
+                write_comment(&mut xml_writer, comment)?;
+            }
+        }
+        if let Some(text) = pending_whitespace.take() {
+            xml_writer.write_event(Event::Text(text))?;
+        }
+        match event {
+            Event::Start(e) if e.name().as_ref() == RESX_B_DATA => {
+                current_tag_name = data_name(&e).filter(|name| tag_value_map.contains_key(name));
+                current_comment = current_tag_name
+                    .as_ref()
+                    .and_then(|tag| comment_map.get(tag))
+                    .map(|comment| comment.trim())
+                    .filter(|comment| !comment.is_empty());
+                xml_writer.write_event(Event::Start(e))?;
+            }
+            Event::Start(e) if e.name().as_ref() == RESX_B_COMMENT && current_comment.is_some() => {
+                // 替换原有的comment
+                let end = e.to_end().into_owned();
+                xml_reader.read_to_end(end.name())?;
+                write_comment(&mut xml_writer, current_comment.take().unwrap())?;
+            }
+            Event::Empty(e) if e.name().as_ref() == RESX_B_COMMENT && current_comment.is_some() => {
+                write_comment(&mut xml_writer, current_comment.take().unwrap())?;
+            }
+            Event::Start(e) if e.name().as_ref() == RESX_B_VALUE && current_tag_name.is_some() => {
+                // 读取原有value内容，再决定是否替换
+                let tag = current_tag_name.take().unwrap();
+                let end = e.to_end().into_owned();
+                let old_value = xml_reader.read_text(end.name())?.to_string();
+                let write_value = write_xml::get_write_value(
+                    &tag,
+                    &tag_value_map[&tag],
+                    default_value_map,
+                    replace_blank_with_default,
+                );
+                xml_writer.write_event(Event::Start(e))?;
+                if write_xml::is_blank(write_value) && write_xml::is_blank(&old_value) {
+                    // 内容均为空，不做修改
+                    xml_writer.write_event(Event::Text(BytesText::from_escaped(old_value)))?;
+                } else {
+                    write_value_text(&mut xml_writer, write_value, &regex)?;
+                }
+                xml_writer.write_event(Event::End(end))?;
+                updated_tags.insert(tag);
+            }
+            Event::Empty(e) if e.name().as_ref() == RESX_B_VALUE && current_tag_name.is_some() => {
+                let tag = current_tag_name.take().unwrap();
+                let write_value = write_xml::get_write_value(
+                    &tag,
+                    &tag_value_map[&tag],
+                    default_value_map,
+                    replace_blank_with_default,
+                );
+                xml_writer.write_event(Event::Start(e.to_owned()))?;
+                write_value_text(&mut xml_writer, write_value, &regex)?;
+                xml_writer.write_event(Event::End(e.to_end()))?;
+                updated_tags.insert(tag);
+            }
+            Event::End(e) => {
+                if e.name().as_ref() == RESX_B_DATA {
+                    current_tag_name = None;
+                    current_comment = None;
+                } else if e.name().as_ref() == RESX_B_ROOT {
+                    // 在关闭root标签前添加缺失的条目
+                    add_missing_data(
+                        &mut xml_writer,
+                        tag_value_map,
+                        default_value_map,
+                        comment_map,
+                        &updated_tags,
+                        parsed_cfg,
+                        &regex,
+                    )?;
+                }
+                xml_writer.write_event(Event::End(e))?;
+            }
+            Event::Eof => break,
+            e => xml_writer.write_event(e)?,
+        }
+    }
+    xml_writer.into_inner().flush()?;
+
+    // 替换原文件
+    remove_file(path)?;
+    rename(temp_path, path)?;
+    Ok(())
+}
+
+/// 获取data的name属性
+fn data_name(e: &BytesStart) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == RESX_B_NAME)
+        .and_then(|attr| attr.unescape_value().ok().map(|v| v.to_string()))
+}
+
+/// 添加缺失的data条目
+fn add_missing_data<W: Write>(
+    xml_writer: &mut Writer<W>,
+    tag_value_map: &HashMap<String, String>,
+    default_value_map: &HashMap<String, String>,
+    comment_map: &HashMap<String, String>,
+    updated_tags: &HashSet<String>,
+    parsed_cfg: &ParsedCfg,
+    regex: &Option<Regex>,
+) -> Result<(), Box<dyn Error>> {
+    let mut missing_tags: Vec<&String> = tag_value_map
+        .keys()
+        .filter(|tag| !updated_tags.contains(*tag))
+        .collect();
+    if missing_tags.is_empty() {
+        return Ok(());
+    }
+    missing_tags.sort();
+    for tag in missing_tags {
+        xml_writer.write_event(Event::Text(BytesText::new(RESX_INDENT)))?;
+        let mut data = BytesStart::new(RESX_DATA);
+        data.push_attribute((RESX_NAME, tag.as_str()));
+        data.push_attribute(("xml:space", "preserve"));
+        xml_writer.write_event(Event::Start(data))?;
+
+        xml_writer.write_event(Event::Text(BytesText::new(RESX_VALUE_INDENT)))?;
+        xml_writer.write_event(Event::Start(BytesStart::new(RESX_VALUE)))?;
+        let write_value = write_xml::get_write_value(
+            tag,
+            &tag_value_map[tag],
+            default_value_map,
+            parsed_cfg.replace_blank_with_default,
+        );
+        write_value_text(xml_writer, write_value, regex)?;
+        xml_writer.write_event(Event::End(BytesEnd::new(RESX_VALUE)))?;
+        if let Some(comment) = comment_map
+            .get(tag)
+            .map(|comment| comment.trim())
+            .filter(|comment| !comment.is_empty())
+        {
+            xml_writer.write_event(Event::Text(BytesText::new(RESX_VALUE_INDENT)))?;
+            write_comment(xml_writer, comment)?;
+        }
+
+        xml_writer.write_event(Event::Text(BytesText::new(RESX_INDENT)))?;
+        xml_writer.write_event(Event::End(BytesEnd::new(RESX_DATA)))?;
+    }
+    xml_writer.write_event(Event::Text(BytesText::new(RESX_NEWLINE)))?;
+    Ok(())
+}
+
+/// 写入comment，文本会自动转义
+fn write_comment<W: Write>(
+    xml_writer: &mut Writer<W>,
+    comment: &str,
+) -> Result<(), Box<dyn Error>> {
+    xml_writer.write_event(Event::Start(BytesStart::new(RESX_COMMENT)))?;
+    xml_writer.write_event(Event::Text(BytesText::new(comment)))?;
+    xml_writer.write_event(Event::End(BytesEnd::new(RESX_COMMENT)))?;
+    Ok(())
+}
+
+/// 写入value文本，文本会自动转义
+fn write_value_text<W: Write>(
+    xml_writer: &mut Writer<W>,
+    value: &str,
+    regex: &Option<Regex>,
+) -> Result<(), Box<dyn Error>> {
+    let value = write_xml::clean_value(value, regex);
+    xml_writer.write_event(Event::Text(BytesText::new(&value)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target::SheetMeta;
+
+    fn map(data: &[(&str, &str)]) -> HashMap<String, String> {
+        data.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// 使用表格数据写入resx文件
+    fn apply(path: &Path, tag_value_map: &HashMap<String, String>, meta: &SheetMeta) {
+        let parsed_cfg = ParsedCfg::from_json(
+            r#"{"tagName":"tag","defaultLang":"en","langMap":{"en":"English"},"escapeOnly":{}}"#,
+        )
+        .unwrap();
+        let entries = LangEntries {
+            lang: "en",
+            tag_value_map,
+            default_value_map: &HashMap::new(),
+            meta,
+        };
+        ResxTarget
+            .apply(path.to_str().unwrap(), &entries, &parsed_cfg)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_update_resx_file() {
+        let path = std::env::temp_dir().join("excel_to_xml_test_update.resx");
+        fs::write(
+            &path,
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n  <data name=\"keep\" xml:space=\"preserve\">\n    <value>Keep</value>\n    <comment>Not in sheet</comment>\n  </data>\n  <data name=\"hello\" xml:space=\"preserve\">\n    <value>Old</value>\n    <comment>Old note</comment>\n  </data>\n  <data name=\"bye\" xml:space=\"preserve\">\n    <value>Old</value>\n  </data>\n</root>\n",
+        )
+        .unwrap();
+        let meta = SheetMeta {
+            comment_map: map(&[("hello", "New <note>"), ("bye", "Farewell")]),
+            ..Default::default()
+        };
+        apply(&path, &map(&[("hello", "Hi & bye"), ("bye", "Bye")]), &meta);
+
+        // 表格中不存在的条目保持不变，comment被替换，没有comment时添加
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n  <data name=\"keep\" xml:space=\"preserve\">\n    <value>Keep</value>\n    <comment>Not in sheet</comment>\n  </data>\n  <data name=\"hello\" xml:space=\"preserve\">\n    <value>Hi &amp; bye</value>\n    <comment>New &lt;note&gt;</comment>\n  </data>\n  <data name=\"bye\" xml:space=\"preserve\">\n    <value>Bye</value>\n    <comment>Farewell</comment>\n  </data>\n</root>\n"
+        );
+    }
+
+    #[test]
+    fn test_new_resx_round_trip() {
+        let path = std::env::temp_dir().join("excel_to_xml_test_new.resx");
+        fs::remove_file(&path).ok();
+        let meta = SheetMeta {
+            comment_map: map(&[("hello", "Greeting")]),
+            ..Default::default()
+        };
+        let tag_value_map = map(&[("hello", "Hello <b>\"world\"</b>"), ("multi", "a\nb")]);
+        apply(&path, &tag_value_map, &meta);
+
+        // 使用模板新建，包含标准resheader
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("<value>text/microsoft-resx</value>"));
+        assert!(content.contains(
+            "  <data name=\"hello\" xml:space=\"preserve\">\n    <value>Hello &lt;b&gt;&quot;world&quot;&lt;/b&gt;</value>\n    <comment>Greeting</comment>\n  </data>"
+        ));
+        let entries: HashMap<String, String> = ResxTarget
+            .read_entries(path.to_str().unwrap())
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(entries, tag_value_map);
+    }
+}
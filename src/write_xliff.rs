@@ -0,0 +1,351 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, create_dir_all, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    config::{self, ParsedCfg},
+    read_excel, read_xliff,
+    target::{self, Entries, LangEntries, LocalizationTarget},
+    write_xml,
+};
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Writer,
+};
+use regex::Regex;
+
+const XLIFF_NS_1_2: &str = "urn:oasis:names:tc:xliff:document:1.2";
+const XLIFF_NS_2_0: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+/// 单个翻译单元
+struct XliffUnit<'a> {
+    id: &'a str,
+    source: String,
+    target: String,
+    note: Option<&'a String>,
+}
+
+/// XLIFF输出目标，每个非默认语言对应一个 <language-tag>.xlf
+/// source为默认语言，target为对应语言，note来自备注列
+pub struct XliffTarget;
+
+impl LocalizationTarget for XliffTarget {
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .filter(|(lang, _)| lang != &parsed_cfg.default_lang)
+            .map(|(lang, _)| {
+                let path = Path::new(dir).join(xliff_file_name(lang));
+                (lang.clone(), path.to_string_lossy().to_string())
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Ok(read_xliff::parse_xliff(&content)?.into_iter().collect())
+    }
+
+    /// 默认语言没有单独的文件，使用所有XLIFF文件中的source，先找到的优先
+    fn read_default_entries(&self, files: &Entries) -> Result<Option<Entries>, Box<dyn Error>> {
+        let mut source_map = HashMap::new();
+        for (_, path) in files {
+            if !Path::new(path).is_file() {
+                continue;
+            }
+            let content = fs::read_to_string(path)?;
+            for (tag, source) in read_xliff::parse_xliff_source(&content)? {
+                source_map.entry(tag).or_insert(source);
+            }
+        }
+        if source_map.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(source_map.into_iter().collect()))
+    }
+
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let regex = write_xml::build_regex(&parsed_cfg.regex);
+        let default_value_map = entries.default_value_map;
+
+        // 按标签排序，保证多次导出结果一致
+        let mut tags: Vec<&String> = default_value_map.keys().collect();
+        tags.sort();
+        let units: Vec<XliffUnit> = tags
+            .into_iter()
+            .map(|tag| XliffUnit {
+                id: tag,
+                source: write_xml::clean_value(&default_value_map[tag], &regex),
+                target: clean_target(entries.tag_value_map.get(tag), &regex),
+                note: entries
+                    .meta
+                    .comment_map
+                    .get(tag)
+                    .filter(|c| !write_xml::is_blank(c)),
+            })
+            .collect();
+
+        let file = File::create(path)?;
+        let mut xml_writer = Writer::new_with_indent(BufWriter::new(file), b' ', 2);
+        if parsed_cfg.xliff_version.starts_with('2') {
+            write_xliff_2_0(&mut xml_writer, parsed_cfg, entries.lang, &units)?;
+        } else {
+            write_xliff_1_2(&mut xml_writer, parsed_cfg, entries.lang, &units)?;
+        }
+        xml_writer.get_mut().write_all(b"\n")?;
+        xml_writer.get_mut().flush()?;
+        println!("写入XLIFF文件: {}", path.display());
+        Ok(())
+    }
+}
+
+/**
+ * 导出XLIFF文件
+ * @param cfg_json 配置JSON
+ * @param excel_path Excel文件路径
+ * @param out_dir 输出文件夹
+ */
+pub fn export(cfg_json: &str, excel_path: &str, out_dir: &str) -> Result<(), Box<dyn Error>> {
+    let parsed_cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json)?;
+    target::quick_update_target(&XliffTarget, &parsed_cfg, excel_path, out_dir)
+}
+
+/// 语言对应的XLIFF文件名
+fn xliff_file_name(lang: &str) -> String {
+    format!("{}.xlf", config::language_tag(lang))
+}
+
+fn clean_target(value: Option<&String>, regex: &Option<Regex>) -> String {
+    value
+        .map(|v| write_xml::clean_value(v, regex))
+        .unwrap_or_default()
+}
+
+/// 写入XLIFF 1.2
+fn write_xliff_1_2<W: Write>(
+    xml_writer: &mut Writer<W>,
+    parsed_cfg: &ParsedCfg,
+    lang: &str,
+    units: &[XliffUnit],
+) -> Result<(), Box<dyn Error>> {
+    xml_writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut root = BytesStart::new("xliff");
+    root.push_attribute(("version", "1.2"));
+    root.push_attribute(("xmlns", XLIFF_NS_1_2));
+    xml_writer.write_event(Event::Start(root))?;
+
+    let source_lang = config::language_tag(&parsed_cfg.default_lang);
+    let target_lang = config::language_tag(lang);
+    let mut file = BytesStart::new("file");
+    file.push_attribute(("original", "strings.xml"));
+    file.push_attribute(("datatype", "plaintext"));
+    file.push_attribute(("source-language", source_lang.as_str()));
+    file.push_attribute(("target-language", target_lang.as_str()));
+    xml_writer.write_event(Event::Start(file))?;
+    xml_writer.write_event(Event::Start(BytesStart::new("body")))?;
+
+    for unit in units {
+        let mut trans_unit = BytesStart::new("trans-unit");
+        trans_unit.push_attribute(("id", unit.id));
+        trans_unit.push_attribute(("resname", unit.id));
+        xml_writer.write_event(Event::Start(trans_unit))?;
+        write_text_element(xml_writer, "source", &unit.source)?;
+        if !write_xml::is_blank(&unit.target) {
+            write_text_element(xml_writer, "target", &unit.target)?;
+        }
+        if let Some(note) = unit.note {
+            write_text_element(xml_writer, "note", note)?;
+        }
+        xml_writer.write_event(Event::End(BytesEnd::new("trans-unit")))?;
+    }
+
+    xml_writer.write_event(Event::End(BytesEnd::new("body")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("file")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("xliff")))?;
+    Ok(())
+}
+
+/// 写入XLIFF 2.0
+fn write_xliff_2_0<W: Write>(
+    xml_writer: &mut Writer<W>,
+    parsed_cfg: &ParsedCfg,
+    lang: &str,
+    units: &[XliffUnit],
+) -> Result<(), Box<dyn Error>> {
+    xml_writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let source_lang = config::language_tag(&parsed_cfg.default_lang);
+    let target_lang = config::language_tag(lang);
+    let mut root = BytesStart::new("xliff");
+    root.push_attribute(("xmlns", XLIFF_NS_2_0));
+    root.push_attribute(("version", "2.0"));
+    root.push_attribute(("srcLang", source_lang.as_str()));
+    root.push_attribute(("trgLang", target_lang.as_str()));
+    xml_writer.write_event(Event::Start(root))?;
+
+    let mut file = BytesStart::new("file");
+    file.push_attribute(("id", "strings"));
+    file.push_attribute(("original", "strings.xml"));
+    xml_writer.write_event(Event::Start(file))?;
+
+    for unit in units {
+        let mut unit_elem = BytesStart::new("unit");
+        unit_elem.push_attribute(("id", unit.id));
+        xml_writer.write_event(Event::Start(unit_elem))?;
+        if let Some(note) = unit.note {
+            xml_writer.write_event(Event::Start(BytesStart::new("notes")))?;
+            write_text_element(xml_writer, "note", note)?;
+            xml_writer.write_event(Event::End(BytesEnd::new("notes")))?;
+        }
+        let mut segment = BytesStart::new("segment");
+        if write_xml::is_blank(&unit.target) {
+            segment.push_attribute(("state", "initial"));
+        } else {
+            segment.push_attribute(("state", "translated"));
+        }
+        xml_writer.write_event(Event::Start(segment))?;
+        write_text_element(xml_writer, "source", &unit.source)?;
+        if !write_xml::is_blank(&unit.target) {
+            write_text_element(xml_writer, "target", &unit.target)?;
+        }
+        xml_writer.write_event(Event::End(BytesEnd::new("segment")))?;
+        xml_writer.write_event(Event::End(BytesEnd::new("unit")))?;
+    }
+
+    xml_writer.write_event(Event::End(BytesEnd::new("file")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("xliff")))?;
+    Ok(())
+}
+
+/// 写入只包含文本的元素，文本会自动转义
+fn write_text_element<W: Write>(
+    xml_writer: &mut Writer<W>,
+    name: &str,
+    text: &str,
+) -> Result<(), Box<dyn Error>> {
+    xml_writer.write_event(Event::Start(BytesStart::new(name)))?;
+    xml_writer.write_event(Event::Text(BytesText::new(text)))?;
+    xml_writer.write_event(Event::End(BytesEnd::new(name)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target::SheetMeta;
+
+    fn map(data: &[(&str, &str)]) -> HashMap<String, String> {
+        data.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// 导出指定版本的XLIFF，返回文件内容
+    fn export(version: &str) -> String {
+        let cfg = format!(
+            r#"{{"tagName":"tag","defaultLang":"en","langMap":{{"en":"English","zh-rTW":"Chinese"}},"escapeOnly":{{}},"xliffVersion":"{}"}}"#,
+            version
+        );
+        let parsed_cfg = ParsedCfg::from_json(&cfg).unwrap();
+        let default_value_map = map(&[
+            ("bold", "<b>Bold</b> & \"quoted\""),
+            ("count", "%d items"),
+            ("empty", "Empty"),
+        ]);
+        let tag_value_map = map(&[("bold", "<b>粗体</b> & \"引号\""), ("count", "%d 項")]);
+        let mut meta = SheetMeta::default();
+        meta.comment_map
+            .insert("count".to_string(), "Shown <in> lists".to_string());
+        let entries = LangEntries {
+            lang: "zh-rTW",
+            tag_value_map: &tag_value_map,
+            default_value_map: &default_value_map,
+            meta: &meta,
+        };
+        let path = std::env::temp_dir().join(format!("excel_to_xml_test_{}.xlf", version));
+        XliffTarget
+            .apply(path.to_str().unwrap(), &entries, &parsed_cfg)
+            .unwrap();
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_xliff_1_2_round_trip() {
+        let content = export("1.2");
+        assert!(
+            content.contains("<source>&lt;b&gt;Bold&lt;/b&gt; &amp; &quot;quoted&quot;</source>")
+        );
+        assert!(content.contains("<note>Shown &lt;in&gt; lists</note>"));
+        // 没有译文的单元不写入target
+        assert_eq!(
+            read_xliff::parse_xliff(&content).unwrap(),
+            map(&[("bold", "<b>粗体</b> & \"引号\""), ("count", "%d 項")])
+        );
+        assert_eq!(
+            read_xliff::parse_xliff_source(&content).unwrap()["empty"],
+            "Empty"
+        );
+
+        // 译者返回的行内标签：g保留文本，无法还原的x跳过该单元
+        let returned = content
+            .replace(
+                "<target>%d 項</target>",
+                "<target><x id=\"1\"/> 項</target>",
+            )
+            .replace(
+                "<source>Empty</source>",
+                "<source>Empty</source>\n        <target>已<g id=\"1\">清空</g></target>",
+            );
+        assert_eq!(returned.matches("<target>").count(), 3);
+        assert_eq!(
+            read_xliff::parse_xliff(&returned).unwrap(),
+            map(&[("bold", "<b>粗体</b> & \"引号\""), ("empty", "已清空")])
+        );
+    }
+
+    #[test]
+    fn test_xliff_2_0_round_trip() {
+        let content = export("2.0");
+        assert!(content.contains("<segment state=\"initial\">"));
+        assert!(content.contains("<notes>"));
+        assert_eq!(
+            read_xliff::parse_xliff(&content).unwrap(),
+            map(&[("bold", "<b>粗体</b> & \"引号\""), ("count", "%d 項")])
+        );
+        assert_eq!(
+            read_xliff::parse_xliff_source(&content).unwrap()["bold"],
+            "<b>Bold</b> & \"quoted\""
+        );
+
+        // 空的行内标签通过originalData还原
+        let returned = content.replace(
+            "<target>%d 項</target>",
+            "<target><ph id=\"1\" dataRef=\"d1\"/> 項</target>",
+        );
+        let returned = returned.replacen(
+            "<unit id=\"count\">",
+            "<unit id=\"count\">\n      <originalData><data id=\"d1\">%d</data></originalData>",
+            1,
+        );
+        assert!(returned.contains("<originalData>"));
+        assert_eq!(
+            read_xliff::parse_xliff(&returned).unwrap()["count"],
+            "%d 項"
+        );
+    }
+}
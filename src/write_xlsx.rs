@@ -0,0 +1,532 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs::{create_dir_all, remove_file, rename, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::xlsx_package;
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/></cellXfs></styleSheet>"#;
+
+const SHEET_NS: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const REL_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/**
+ * 写入只有一个工作表的xlsx文件，所有单元格均为文本
+ * 第一行作为表头，空字符串的单元格不写入
+ * @param path xlsx文件路径
+ * @param sheet_name 工作表名称
+ * @param rows 表格数据
+ */
+pub fn write_xlsx(
+    path: &str,
+    sheet_name: &str,
+    rows: &[Vec<String>],
+) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        create_dir_all(parent)?;
+    }
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(CONTENT_TYPES.as_bytes())?;
+    zip.start_file("_rels/.rels", options)?;
+    zip.write_all(ROOT_RELS.as_bytes())?;
+    zip.start_file("xl/_rels/workbook.xml.rels", options)?;
+    zip.write_all(WORKBOOK_RELS.as_bytes())?;
+    zip.start_file("xl/styles.xml", options)?;
+    zip.write_all(STYLES.as_bytes())?;
+
+    zip.start_file("xl/workbook.xml", options)?;
+    write_workbook_xml(&mut zip, sheet_name)?;
+    zip.start_file("xl/worksheets/sheet1.xml", options)?;
+    write_sheet_xml(&mut zip, rows)?;
+
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+/**
+ * 写入表格，.csv 后缀写入CSV（UTF-8 BOM），其余写入xlsx
+ * @param path 输出文件路径
+ * @param sheet_name 工作表名称，CSV忽略
+ * @param rows 表格数据
+ */
+pub fn write_table(
+    path: &str,
+    sheet_name: &str,
+    rows: &[Vec<String>],
+) -> Result<(), Box<dyn Error>> {
+    let is_csv = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    if !is_csv {
+        return write_xlsx(path, sheet_name, rows);
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    // 写入BOM，Excel打开时才能正确识别UTF-8
+    writer.write_all("\u{feff}".as_bytes())?;
+    for cells in rows {
+        let line: Vec<String> = cells.iter().map(|cell| escape_csv(cell)).collect();
+        writer.write_all(line.join(",").as_bytes())?;
+        writer.write_all(b"\r\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// CSV字段转义，包含逗号、引号或换行时使用引号包裹
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 写入workbook.xml，只包含一个工作表
+fn write_workbook_xml<W: Write>(out: W, sheet_name: &str) -> Result<(), Box<dyn Error>> {
+    let mut xml_writer = Writer::new(out);
+    xml_writer.write_event(Event::Decl(BytesDecl::new(
+        "1.0",
+        Some("UTF-8"),
+        Some("yes"),
+    )))?;
+    let mut workbook = BytesStart::new("workbook");
+    workbook.push_attribute(("xmlns", SHEET_NS));
+    workbook.push_attribute(("xmlns:r", REL_NS));
+    xml_writer.write_event(Event::Start(workbook))?;
+    xml_writer.write_event(Event::Start(BytesStart::new("sheets")))?;
+    let mut sheet = BytesStart::new("sheet");
+    sheet.push_attribute(("name", sheet_name));
+    sheet.push_attribute(("sheetId", "1"));
+    sheet.push_attribute(("r:id", "rId1"));
+    xml_writer.write_event(Event::Empty(sheet))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("sheets")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("workbook")))?;
+    Ok(())
+}
+
+/// 写入工作表数据，单元格使用内联字符串
+fn write_sheet_xml<W: Write>(out: W, rows: &[Vec<String>]) -> Result<(), Box<dyn Error>> {
+    let mut xml_writer = Writer::new(out);
+    xml_writer.write_event(Event::Decl(BytesDecl::new(
+        "1.0",
+        Some("UTF-8"),
+        Some("yes"),
+    )))?;
+    let mut worksheet = BytesStart::new("worksheet");
+    worksheet.push_attribute(("xmlns", SHEET_NS));
+    worksheet.push_attribute(("xmlns:r", REL_NS));
+    xml_writer.write_event(Event::Start(worksheet))?;
+    xml_writer.write_event(Event::Start(BytesStart::new("sheetData")))?;
+
+    for (row_index, cells) in rows.iter().enumerate() {
+        let row_ref = (row_index + 1).to_string();
+        let mut row = BytesStart::new("row");
+        row.push_attribute(("r", row_ref.as_str()));
+        xml_writer.write_event(Event::Start(row))?;
+        for (col_index, value) in cells.iter().enumerate() {
+            if value.is_empty() {
+                continue;
+            }
+            let cell_ref = format!("{}{}", column_name(col_index as u32), row_ref);
+            write_inline_cell(&mut xml_writer, &cell_ref, None, value)?;
+        }
+        xml_writer.write_event(Event::End(BytesEnd::new("row")))?;
+    }
+
+    xml_writer.write_event(Event::End(BytesEnd::new("sheetData")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("worksheet")))?;
+    Ok(())
+}
+
+/// 写入内联字符串单元格，style为原单元格的样式序号
+fn write_inline_cell<W: Write>(
+    xml_writer: &mut Writer<W>,
+    cell_ref: &str,
+    style: Option<&str>,
+    value: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut cell = BytesStart::new("c");
+    cell.push_attribute(("r", cell_ref));
+    if let Some(style) = style {
+        cell.push_attribute(("s", style));
+    }
+    cell.push_attribute(("t", "inlineStr"));
+    xml_writer.write_event(Event::Start(cell))?;
+    xml_writer.write_event(Event::Start(BytesStart::new("is")))?;
+    let mut text = BytesStart::new("t");
+    if value.trim() != value {
+        // 保留首尾空白
+        text.push_attribute(("xml:space", "preserve"));
+    }
+    xml_writer.write_event(Event::Start(text))?;
+    xml_writer.write_event(Event::Text(BytesText::new(value)))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("t")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("is")))?;
+    xml_writer.write_event(Event::End(BytesEnd::new("c")))?;
+    Ok(())
+}
+
+/**
+ * 修改已有xlsx文件中的单元格，其他内容（样式、其他工作表等）保持不变
+ * 修改后的单元格保留原样式，内容写为内联字符串
+ * @param path xlsx文件路径
+ * @param sheet_name 工作表名称，为空时使用第一个工作表
+ * @param updates (行, 列) - 新值，行列均从0开始
+ * @return 实际修改的单元格数量
+ */
+pub fn update_xlsx_cells(
+    path: &str,
+    sheet_name: &str,
+    updates: &HashMap<(u32, u32), String>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut archive = xlsx_package::open_package(path)?;
+    let sheet_path = xlsx_package::sheet_xml_path(&mut archive, sheet_name)?;
+    let sheet_xml = xlsx_package::read_part(&mut archive, &sheet_path)?;
+    let (patched, count) = patch_sheet_xml(&sheet_xml, updates)?;
+
+    let temp_path = format!("{}.temp", path);
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(&temp_path)?));
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        if file.name() == sheet_path {
+            let options = SimpleFileOptions::default();
+            let name = file.name().to_string();
+            drop(file);
+            zip.start_file(name, options)?;
+            zip.write_all(&patched)?;
+        } else {
+            zip.raw_copy_file(file)?;
+        }
+    }
+    zip.finish()?.flush()?;
+    drop(archive);
+
+    // 替换原文件
+    remove_file(path)?;
+    rename(temp_path, path)?;
+    Ok(count)
+}
+
+/// 修改工作表xml中的单元格，原本不存在的单元格按列顺序插入，原本不存在的行按行顺序插入
+fn patch_sheet_xml(
+    sheet_xml: &str,
+    updates: &HashMap<(u32, u32), String>,
+) -> Result<(Vec<u8>, usize), Box<dyn Error>> {
+    let mut row_updates: BTreeMap<u32, BTreeMap<u32, &String>> = BTreeMap::new();
+    for ((row, col), value) in updates {
+        row_updates.entry(*row).or_default().insert(*col, value);
+    }
+
+    let mut xml_reader = Reader::from_str(sheet_xml);
+    let mut xml_writer = Writer::new(Vec::new());
+    let mut count = 0;
+    let mut cur_row: u32 = 0;
+    let mut next_col: u32 = 0;
+    let mut pending: BTreeMap<u32, &String> = BTreeMap::new();
+    // 跳过被替换单元格的原内容
+    let mut skip_depth = 0;
+
+    loop {
+        let event = xml_reader.read_event()?;
+        if skip_depth > 0 {
+            match event {
+                Event::Start(_) => skip_depth += 1,
+                Event::End(_) => skip_depth -= 1,
+                Event::Eof => break,
+                _ => {}
+            }
+            continue;
+        }
+        match event {
+            Event::Start(e) if e.local_name().as_ref() == b"row" => {
+                cur_row = xlsx_package::row_index(&e).unwrap_or(cur_row + 1);
+                count += write_new_rows(&mut xml_writer, &mut row_updates, Some(cur_row))?;
+                next_col = 0;
+                pending = row_updates.remove(&cur_row).unwrap_or_default();
+                xml_writer.write_event(Event::Start(e))?;
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"row" => {
+                cur_row = xlsx_package::row_index(&e).unwrap_or(cur_row + 1);
+                count += write_new_rows(&mut xml_writer, &mut row_updates, Some(cur_row))?;
+                match row_updates.remove(&cur_row) {
+                    Some(cells) => {
+                        xml_writer.write_event(Event::Start(e.to_owned()))?;
+                        for (col, value) in cells {
+                            let cell_ref = format!("{}{}", column_name(col), cur_row + 1);
+                            write_inline_cell(&mut xml_writer, &cell_ref, None, value)?;
+                            count += 1;
+                        }
+                        xml_writer.write_event(Event::End(e.to_end()))?;
+                    }
+                    None => xml_writer.write_event(Event::Empty(e))?,
+                }
+            }
+            Event::Start(ref e) | Event::Empty(ref e) if e.local_name().as_ref() == b"c" => {
+                let col = xlsx_package::attr_value(e, b"r")
+                    .and_then(|r| xlsx_package::cell_position(&r))
+                    .map(|(_, col)| col)
+                    .unwrap_or(next_col);
+                next_col = col + 1;
+                // 先插入原本不存在的单元格
+                while let Some((&pending_col, _)) = pending.first_key_value() {
+                    if pending_col >= col {
+                        break;
+                    }
+                    let value = pending.remove(&pending_col).unwrap();
+                    let cell_ref = format!("{}{}", column_name(pending_col), cur_row + 1);
+                    write_inline_cell(&mut xml_writer, &cell_ref, None, value)?;
+                    count += 1;
+                }
+                match pending.remove(&col) {
+                    Some(value) => {
+                        let cell_ref = format!("{}{}", column_name(col), cur_row + 1);
+                        let style = xlsx_package::attr_value(e, b"s");
+                        write_inline_cell(&mut xml_writer, &cell_ref, style.as_deref(), value)?;
+                        count += 1;
+                        if matches!(event, Event::Start(_)) {
+                            skip_depth = 1;
+                        }
+                    }
+                    None => xml_writer.write_event(event)?,
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"row" => {
+                for (col, value) in std::mem::take(&mut pending) {
+                    let cell_ref = format!("{}{}", column_name(col), cur_row + 1);
+                    write_inline_cell(&mut xml_writer, &cell_ref, None, value)?;
+                    count += 1;
+                }
+                xml_writer.write_event(Event::End(e))?;
+            }
+            Event::End(e) if e.local_name().as_ref() == b"sheetData" => {
+                count += write_new_rows(&mut xml_writer, &mut row_updates, None)?;
+                xml_writer.write_event(Event::End(e))?;
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"sheetData" => {
+                xml_writer.write_event(Event::Start(e.to_owned()))?;
+                count += write_new_rows(&mut xml_writer, &mut row_updates, None)?;
+                xml_writer.write_event(Event::End(e.to_end()))?;
+            }
+            Event::Eof => break,
+            e => xml_writer.write_event(e)?,
+        }
+    }
+    Ok((xml_writer.into_inner(), count))
+}
+
+/// 写入原本不存在的行，before为下一个已有行的序号，为None时写入剩余的所有行，返回写入的单元格数量
+fn write_new_rows<W: Write>(
+    xml_writer: &mut Writer<W>,
+    row_updates: &mut BTreeMap<u32, BTreeMap<u32, &String>>,
+    before: Option<u32>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+    while let Some(entry) = row_updates.first_entry() {
+        if before.is_some_and(|before| *entry.key() >= before) {
+            break;
+        }
+        let (row_index, cells) = entry.remove_entry();
+        let row_ref = (row_index + 1).to_string();
+        let mut row = BytesStart::new("row");
+        row.push_attribute(("r", row_ref.as_str()));
+        xml_writer.write_event(Event::Start(row))?;
+        for (col, value) in cells {
+            let cell_ref = format!("{}{}", column_name(col), row_ref);
+            write_inline_cell(xml_writer, &cell_ref, None, value)?;
+            count += 1;
+        }
+        xml_writer.write_event(Event::End(BytesEnd::new("row")))?;
+    }
+    Ok(count)
+}
+
+/// 列序号转换为Excel列名，0 -> A，26 -> AA
+pub fn column_name(index: u32) -> String {
+    let mut name = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.push(b'A' + rem as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::{open_workbook, Reader, Xlsx};
+
+    #[test]
+    fn test_write_xlsx() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+
+        let path = std::env::temp_dir().join("excel_to_xml_test_write.xlsx");
+        let path = path.to_string_lossy().to_string();
+        let rows = vec![
+            vec!["Android tag".to_string(), "英语".to_string()],
+            vec!["hello".to_string(), " Hello <b> & \"x\"\n".to_string()],
+            vec!["empty".to_string(), String::new()],
+        ];
+        write_xlsx(&path, "Strings", &rows).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert_eq!(workbook.sheet_names(), vec!["Strings"]);
+        let range = workbook.worksheet_range("Strings").unwrap();
+        assert_eq!(range.get_value((1, 1)).unwrap().to_string(), rows[1][1]);
+        assert_eq!(range.get_value((2, 0)).unwrap().to_string(), "empty");
+        std::fs::remove_file(&path).ok();
+    }
+
+    /// 测试用的修改，(行, 列) - 新值
+    fn updates(cells: &[((u32, u32), &str)]) -> HashMap<(u32, u32), String> {
+        cells
+            .iter()
+            .map(|(pos, value)| (*pos, value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_patch_sheet_xml() {
+        let sheet_xml = r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="C1" s="1" t="s"><v>1</v></c></row><row r="4"><c r="B4" s="2" t="inlineStr"><is><t>old</t></is></c></row></sheetData></worksheet>"#;
+        let (patched, count) = patch_sheet_xml(
+            sheet_xml,
+            &updates(&[
+                ((0, 1), "new B1"),
+                ((0, 2), "new C1"),
+                ((1, 0), "new A2"),
+                ((3, 0), "new A4"),
+                ((3, 1), "new B4"),
+                ((5, 2), "new C6"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(count, 6);
+        // 替换的单元格保留样式，插入的单元格和行按顺序排列
+        assert_eq!(
+            String::from_utf8(patched).unwrap(),
+            concat!(
+                r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c>"#,
+                r#"<c r="B1" t="inlineStr"><is><t>new B1</t></is></c>"#,
+                r#"<c r="C1" s="1" t="inlineStr"><is><t>new C1</t></is></c></row>"#,
+                r#"<row r="2"><c r="A2" t="inlineStr"><is><t>new A2</t></is></c></row>"#,
+                r#"<row r="4"><c r="A4" t="inlineStr"><is><t>new A4</t></is></c>"#,
+                r#"<c r="B4" s="2" t="inlineStr"><is><t>new B4</t></is></c></row>"#,
+                r#"<row r="6"><c r="C6" t="inlineStr"><is><t>new C6</t></is></c></row>"#,
+                r#"</sheetData></worksheet>"#
+            )
+        );
+
+        let (patched, count) = patch_sheet_xml(
+            "<worksheet><sheetData/></worksheet>",
+            &updates(&[((0, 0), "a")]),
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            String::from_utf8(patched).unwrap(),
+            r#"<worksheet><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>a</t></is></c></row></sheetData></worksheet>"#
+        );
+    }
+
+    #[test]
+    fn test_update_xlsx_cells() {
+        let path = std::env::temp_dir().join("excel_to_xml_test_update.xlsx");
+        let path = path.to_string_lossy().to_string();
+        let content_types = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/><Override PartName="/xl/sharedStrings.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml"/></Types>"#;
+        let workbook_rels = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/></Relationships>"#;
+        let shared_strings = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="2" uniqueCount="2"><si><t>tag</t></si><si><t>English</t></si></sst>"#;
+        let sheet = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="{}"><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row><row r="2"><c r="A2" t="inlineStr"><is><t>hello</t></is></c><c r="B2" s="0" t="inlineStr"><is><t>Old</t></is></c></row></sheetData></worksheet>"#,
+            SHEET_NS
+        );
+        let mut workbook_xml = Vec::new();
+        write_workbook_xml(&mut workbook_xml, "Strings").unwrap();
+        let parts: Vec<(&str, Vec<u8>)> = vec![
+            ("[Content_Types].xml", content_types.as_bytes().to_vec()),
+            ("_rels/.rels", ROOT_RELS.as_bytes().to_vec()),
+            (
+                "xl/_rels/workbook.xml.rels",
+                workbook_rels.as_bytes().to_vec(),
+            ),
+            ("xl/styles.xml", STYLES.as_bytes().to_vec()),
+            ("xl/sharedStrings.xml", shared_strings.as_bytes().to_vec()),
+            ("xl/workbook.xml", workbook_xml),
+            ("xl/worksheets/sheet1.xml", sheet.into_bytes()),
+            ("xl/media/image1.bin", (0..=255).collect()),
+        ];
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        for (name, data) in &parts {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let count = update_xlsx_cells(
+            &path,
+            "Strings",
+            &updates(&[((1, 1), "New"), ((1, 2), "Neu"), ((2, 0), "bye")]),
+        )
+        .unwrap();
+        assert_eq!(count, 3);
+
+        // 共享字符串和内联字符串单元格都能正确读取
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let range = workbook.worksheet_range("Strings").unwrap();
+        let value = |row: u32, col: u32| range.get_value((row, col)).unwrap().to_string();
+        assert_eq!(value(0, 0), "tag");
+        assert_eq!(value(0, 1), "English");
+        assert_eq!(value(1, 0), "hello");
+        assert_eq!(value(1, 1), "New");
+        assert_eq!(value(1, 2), "Neu");
+        assert_eq!(value(2, 0), "bye");
+
+        // 除工作表外的其他文件保持不变
+        let mut archive = xlsx_package::open_package(&path).unwrap();
+        assert_eq!(archive.len(), parts.len());
+        for (name, data) in &parts {
+            if *name == "xl/worksheets/sheet1.xml" {
+                continue;
+            }
+            let mut bytes = Vec::new();
+            std::io::Read::read_to_end(&mut archive.by_name(name).unwrap(), &mut bytes).unwrap();
+            assert_eq!(&bytes, data, "{}", name);
+        }
+        let sheet_xml = xlsx_package::read_part(&mut archive, "xl/worksheets/sheet1.xml").unwrap();
+        assert!(sheet_xml.contains(r#"<c r="B2" s="0" t="inlineStr"><is><t>New</t></is></c>"#));
+        std::fs::remove_file(&path).ok();
+    }
+}
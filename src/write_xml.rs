@@ -3,10 +3,15 @@ use std::{
     error::Error,
     fs::{remove_file, rename, File},
     io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
 };
 
-use crate::{config::ParsedCfg, find_files, read_excel};
-use calamine::{open_workbook, Reader, Xlsx};
+use crate::{
+    config::ParsedCfg,
+    find_files,
+    source::RowMeta,
+    target::{Entries, LangEntries, LocalizationTarget, SheetMeta},
+};
 use quick_xml::{
     events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
     Writer,
@@ -15,6 +20,9 @@ use regex::Regex;
 
 const XML_NAME: &str = "name";
 const XML_B_NAME: &[u8] = b"name";
+const XML_B_TRANSLATABLE: &[u8] = b"translatable";
+const XML_B_TOOLS_IGNORE: &[u8] = b"tools:ignore";
+const XML_B_FORMATTED: &[u8] = b"formatted";
 // 格式化相关常量
 const XML_NEWLINE: &str = "\n";
 const XML_INDENT: &str = "\n    ";
@@ -22,228 +30,352 @@ const XML_STRING: &str = "string";
 const XML_B_STRING: &[u8] = b"string";
 const XML_RESOURCES: &str = "resources";
 const XML_B_RESOURCES: &[u8] = b"resources";
+/// 工具写入的注释的开头，只替换带有该标记的注释
+pub const COMMENT_MARKER: &str = "[excel_to_xml]";
+
+/// Android strings.xml 输出目标
+pub struct AndroidTarget;
+
+impl LocalizationTarget for AndroidTarget {
+    /// 查找res文件夹下各语言的strings.xml，不存在的语言跳过
+    fn discover(&self, dir: &str, parsed_cfg: &ParsedCfg) -> Result<Entries, Box<dyn Error>> {
+        let paths = find_xml_paths(parsed_cfg, dir)?;
+        Ok(parsed_cfg
+            .lang_map
+            .iter()
+            .filter_map(|(lang, _)| {
+                find_lang_path(&paths, lang, &parsed_cfg.default_lang)
+                    .map(|path| (lang.clone(), path.clone()))
+            })
+            .collect())
+    }
+
+    fn read_entries(&self, path: &str) -> Result<Entries, Box<dyn Error>> {
+        read_xml_entries(path)
+    }
+
+    /// 表格、默认语言或当前文件中标记为不需要翻译的标签，不写入其他语言
+    fn skipped_tags(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<HashSet<String>, Box<dyn Error>> {
+        if entries.lang == parsed_cfg.default_lang {
+            return Ok(HashSet::new());
+        }
+        // tools:ignore只在默认语言中有效
+        let mut skipped = non_translatable_names(path, false)?;
+        if let Some(default_path) = default_xml_path(path) {
+            skipped.extend(non_translatable_names(
+                &default_path.to_string_lossy(),
+                true,
+            )?);
+        }
+        skipped.extend(
+            entries
+                .meta
+                .translatable_map
+                .iter()
+                .filter(|(_, translatable)| !**translatable)
+                .map(|(tag, _)| tag.clone()),
+        );
+        // 表格中标记为需要翻译的，以表格为准
+        skipped.retain(|tag| entries.meta.translatable_map.get(tag) != Some(&true));
+        Ok(skipped)
+    }
+
+    fn apply(
+        &self,
+        path: &str,
+        entries: &LangEntries,
+        parsed_cfg: &ParsedCfg,
+    ) -> Result<(), Box<dyn Error>> {
+        let string_meta = StringMeta {
+            meta: entries.meta,
+            is_default: entries.lang == parsed_cfg.default_lang,
+        };
+        update_xml_file(
+            path,
+            entries.tag_value_map,
+            entries.default_value_map,
+            parsed_cfg,
+            &string_meta,
+        )
+    }
+}
 
-#[derive(Default)]
-struct PathIndex {
-    path: String,
-    index: u32,
-    lang: String,
+/// 表格中与string标签相关的信息，标记列写入 translatable 和 formatted 属性，备注列写入注释
+struct StringMeta<'a> {
+    meta: &'a SheetMeta,
+    is_default: bool, // translatable只写入默认语言
 }
 
-/// 获取解析后的数据
-/// - excel_path: Excel文件路径
-/// - parsed_cfg: 解析后的配置
-/// - paths: 找到的XML文件路径列表
-fn get_parsed_data<'a>(
-    cfg_json: &str,
-    excel_path: &'a str,
+impl StringMeta<'_> {
+    /**
+     * 按标记更新string标签的属性，其他属性保持不变
+     * 标记为false时添加或更新属性，标记为true时移除属性，未标记时保持原样
+     * @param e 原有的string标签
+     * @param tag 标签名称
+     * @return 更新后的string标签
+     */
+    fn apply(&self, e: &BytesStart, tag: &str) -> BytesStart<'static> {
+        let translatable = self
+            .meta
+            .translatable_map
+            .get(tag)
+            .filter(|_| self.is_default);
+        let flags = [
+            (XML_B_TRANSLATABLE, translatable),
+            (XML_B_FORMATTED, self.meta.formatted_map.get(tag)),
+        ];
+        let mut elem = BytesStart::new(XML_STRING);
+        let mut written = [false; 2];
+        for attr in e.attributes().flatten() {
+            match flags.iter().position(|(key, _)| *key == attr.key.as_ref()) {
+                Some(i) => match flags[i].1 {
+                    Some(true) => {}
+                    Some(false) => {
+                        elem.push_attribute((attr.key.as_ref(), b"false".as_slice()));
+                        written[i] = true;
+                    }
+                    None => elem.push_attribute(attr),
+                },
+                None => elem.push_attribute(attr),
+            }
+        }
+        for (i, (key, flag)) in flags.iter().enumerate() {
+            if *flag == Some(&false) && !written[i] {
+                elem.push_attribute((*key, b"false".as_slice()));
+            }
+        }
+        elem
+    }
+
+    /// 标签对应的注释，以标记开头以便再次同步时替换，备注为空时返回None
+    fn comment(&self, tag: &str) -> Option<Event<'static>> {
+        let comment = self.meta.comment_map.get(tag)?.trim();
+        if comment.is_empty() {
+            return None;
+        }
+        // 前后的空格保证注释不以 - 结尾
+        Some(Event::Comment(BytesText::from_escaped(format!(
+            " {} {} ",
+            COMMENT_MARKER,
+            escape_comment(comment)
+        ))))
+    }
+}
+
+/// 转义注释内容，注释中不能出现 --，在每个紧跟 - 的 - 前插入空格
+fn escape_comment(comment: &str) -> String {
+    let mut result = String::with_capacity(comment.len());
+    let mut prev = None;
+    for c in comment.chars() {
+        if c == '-' && prev == Some('-') {
+            result.push(' ');
+        }
+        result.push(c);
+        prev = Some(c);
+    }
+    result
+}
+
+/// 查找res文件夹下所有的strings.xml
+pub fn find_xml_paths(
+    parsed_cfg: &ParsedCfg,
     xml_dir_path: &str,
-) -> Result<(&'a str, ParsedCfg, Vec<String>), Box<dyn Error>> {
-    let cfg = read_excel::parse_cfg_with_excel(excel_path, cfg_json);
-    if cfg.is_err() {
-        println!("解析配置时出错: {:?}", cfg.err());
-        return Err("解析配置时出错".into());
-    }
-    let parsed_cfg = cfg.unwrap();
-    println!("解析配置成功: {:?}", parsed_cfg);
+) -> Result<Vec<String>, Box<dyn Error>> {
     let ignore_folders: Vec<&str> = parsed_cfg
         .ignore_folder
         .iter()
         .map(|s| s.as_str())
         .collect();
-    let forder = find_files::find_target_folder(&xml_dir_path, "res", &ignore_folders);
+    let forder = find_files::find_target_folder(xml_dir_path, "res", &ignore_folders);
     if forder.is_none() {
         println!("未找到res文件夹");
         return Err("未找到res文件夹".into());
     }
     let res_folder = forder.unwrap();
     println!("找到res文件夹: {}", res_folder);
-    let paths = find_files::collect_target_files(&res_folder, "values", "strings.xml");
-    return Ok((excel_path, parsed_cfg, paths));
+    Ok(find_files::collect_target_files(
+        &res_folder,
+        "values",
+        "strings.xml",
+    ))
 }
 
-/// 准备需要写入的数据
-pub fn update(
-    cfg_json: &str,
-    excel_path: &str,
-    xml_dir_path: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let (file_path, parsed_cfg, paths) = get_parsed_data(cfg_json, excel_path, xml_dir_path)?;
-    let tag_index = parsed_cfg.tag_index;
-    // 预先打开Excel文件，只打开一次
-    let mut workbook: Xlsx<_> = open_workbook(file_path)?;
-
-    // 获取sheet名称（只需获取一次）
-    let sheet_name = if parsed_cfg.sheet_name.is_empty() {
-        workbook
-            .sheet_names()
-            .get(0)
-            .cloned()
-            .ok_or("No sheets found")?
+/// 查找语言对应的strings.xml路径
+fn find_lang_path<'a>(paths: &'a [String], lang: &str, default_lang: &str) -> Option<&'a String> {
+    let end_point = if lang == default_lang {
+        "values/strings.xml".to_string()
     } else {
-        parsed_cfg.sheet_name.clone()
+        format!("values-{}/strings.xml", lang)
     };
+    paths.iter().find(|path| path.ends_with(&end_point))
+}
 
-    // 预分配一个HashMap，循环内复用
-    let mut tag_value_map = HashMap::with_capacity(5000);
-
-    let mut default_valug_map: HashMap<String, String> = HashMap::new();
-    let default_lang = &parsed_cfg.default_lang;
-    let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
-
-    let mut lang_index_map = parsed_cfg.lang_index_map.clone();
-    // 将默认语言移到第一个位置
-    if let Some(pos) = lang_index_map
-        .iter()
-        .position(|(lang, _)| &lang == &default_lang)
-    {
-        // 将默认语言换到第一个位置
-        lang_index_map.swap(0, pos);
+/// 读取strings.xml中的string条目，值还原XML实体和Android转义
+fn read_xml_entries(path: &str) -> Result<Entries, Box<dyn Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut xml_reader = quick_xml::Reader::from_str(&content);
+    let mut entries = Vec::new();
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) if e.name().as_ref() == XML_B_STRING => {
+                let end = e.to_end().into_owned();
+                // string中可能包含<b>等标签，读取原始内容
+                let raw = xml_reader.read_text(end.name())?;
+                if let Some(name) = string_name(&e) {
+                    let text = quick_xml::escape::unescape(&raw)
+                        .map(|t| t.to_string())
+                        .unwrap_or_else(|_| raw.to_string());
+                    entries.push((name, unescape_android(&text)));
+                }
+            }
+            Event::Empty(e) if e.name().as_ref() == XML_B_STRING => {
+                if let Some(name) = string_name(&e) {
+                    entries.push((name, String::new()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
     }
+    Ok(entries)
+}
 
-    // 遍历语言索引映射
-    for (lang, index) in &lang_index_map {
-        let is_default_lang = lang == default_lang;
-        let end_point = if is_default_lang {
-            "values/strings.xml".to_string()
-        } else {
-            format!("values-{}/strings.xml", lang)
-        };
-
-        // 查找匹配的XML文件路径
-        let path = match paths.iter().find(|path| path.ends_with(&end_point)) {
-            Some(path) => path,
-            None => continue, // 没找到对应语言的文件，跳过这个语言
-        };
+/// 同一个res文件夹下默认语言的strings.xml，values-xx/strings.xml -> values/strings.xml
+fn default_xml_path(path: &str) -> Option<PathBuf> {
+    let res_dir = Path::new(path).parent()?.parent()?;
+    Some(res_dir.join("values").join("strings.xml"))
+}
 
-        // 清空map，准备复用
-        tag_value_map.clear();
-
-        // 构建标签值映射 - 复用workbook、sheet_name和tag_value_map
-        let lang_index = *index;
-        read_excel::process_excel_single_lang(
-            &mut workbook,
-            &sheet_name,
-            tag_index,
-            lang_index,
-            &mut tag_value_map,
-        )?;
-        // 如果是默认语言，将tag_value_map的内容复制到default_valug_map
-        // 以便后续处理空值
-        if is_default_lang && replace_blank_with_default {
-            // 处理默认语言,使用空default_valug_map
-            update_xml_file(path, &tag_value_map, &default_valug_map, &parsed_cfg)?;
-            default_valug_map = tag_value_map.clone();
-        } else {
-            // 处理XML文件
-            update_xml_file(path, &tag_value_map, &default_valug_map, &parsed_cfg)?;
+/**
+ * 读取标记为不需要翻译的string名称
+ * translatable="false"，或 tools:ignore 包含 MissingTranslation
+ * @param path strings.xml路径，文件不存在时返回空集合
+ * @param with_tools_ignore 是否读取 tools:ignore，只有默认语言中的有效
+ */
+pub fn non_translatable_names(
+    path: &str,
+    with_tools_ignore: bool,
+) -> Result<HashSet<String>, Box<dyn Error>> {
+    let mut names = HashSet::new();
+    if !Path::new(path).is_file() {
+        return Ok(names);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let mut xml_reader = quick_xml::Reader::from_str(&content);
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.name().as_ref() == XML_B_STRING => {
+                let non_translatable = e.attributes().flatten().any(|attr| {
+                    let value = attr.unescape_value().unwrap_or_default();
+                    match attr.key.as_ref() {
+                        XML_B_TRANSLATABLE => value == "false",
+                        XML_B_TOOLS_IGNORE => {
+                            with_tools_ignore && value.contains("MissingTranslation")
+                        }
+                        _ => false,
+                    }
+                });
+                if non_translatable {
+                    if let Some(name) = string_name(&e) {
+                        names.insert(name);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
         }
     }
-    tag_value_map.clear(); // 清空map
-    tag_value_map.shrink_to_fit(); // 释放内存
-    Ok(())
+    Ok(names)
 }
 
-/// 快速更新，占用更多内存
-pub fn quick_update(
-    cfg_json: &str,
-    excel_path: &str,
-    xml_dir_path: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let (file_path, parsed_cfg, paths) = get_parsed_data(cfg_json, excel_path, xml_dir_path)?;
-    let tag_index = parsed_cfg.tag_index;
-
-    // 预先打开Excel文件，只打开一次
-    let mut workbook: Xlsx<_> = open_workbook(file_path)?;
-
-    // 获取sheet名称（只需获取一次）
-    let sheet_name = if parsed_cfg.sheet_name.is_empty() {
-        workbook
-            .sheet_names()
-            .get(0)
-            .cloned()
-            .ok_or("No sheets found")?
-    } else {
-        parsed_cfg.sheet_name.clone()
-    };
+/**
+ * 读取每个string的备注和标记，备注为紧挨在string前面的注释
+ * @param path strings.xml路径，文件不存在时返回空映射
+ * @return name - 标签信息，没有备注和标记的string不包含
+ */
+pub fn read_string_meta(path: &str) -> Result<HashMap<String, RowMeta>, Box<dyn Error>> {
+    let mut meta_map = HashMap::new();
+    if !Path::new(path).is_file() {
+        return Ok(meta_map);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let mut xml_reader = quick_xml::Reader::from_str(&content);
+    let mut last_comment: Option<String> = None;
+    loop {
+        match xml_reader.read_event()? {
+            Event::Comment(e) => {
+                let comment = String::from_utf8_lossy(&e);
+                let comment = comment.trim();
+                let comment = comment.strip_prefix(COMMENT_MARKER).unwrap_or(comment);
+                last_comment = Some(comment.trim_start().to_string());
+            }
+            Event::Text(e) => {
+                // 注释与string之间只能有空白，且最多换一行
+                let text = String::from_utf8_lossy(&e);
+                if !text.trim().is_empty() || text.matches('\n').count() > 1 {
+                    last_comment = None;
+                }
+            }
+            Event::Start(e) | Event::Empty(e) if e.name().as_ref() == XML_B_STRING => {
+                let mut meta = RowMeta {
+                    comment: last_comment.take(),
+                    ..Default::default()
+                };
+                for attr in e.attributes().flatten() {
+                    let flag = match attr.unescape_value().as_deref() {
+                        Ok("true") => Some(true),
+                        Ok("false") => Some(false),
+                        _ => None,
+                    };
+                    match attr.key.as_ref() {
+                        XML_B_TRANSLATABLE => meta.translatable = flag,
+                        XML_B_FORMATTED => meta.formatted = flag,
+                        _ => {}
+                    }
+                }
+                if let Some(name) = string_name(&e) {
+                    if meta != RowMeta::default() {
+                        meta_map.insert(name, meta);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => last_comment = None,
+        }
+    }
+    Ok(meta_map)
+}
 
-    // 预分配一个HashMap，循环内复用
-    let mut tag_value_map: HashMap<String, HashMap<u32, String>> = HashMap::with_capacity(5000);
-    let mut path_index_vec: Vec<PathIndex> = Vec::with_capacity(paths.len());
-    let default_lang = &parsed_cfg.default_lang;
-    let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
-    let mut default_valug_map: HashMap<String, String> = HashMap::new();
-    // 遍历语言索引映射
-    for (lang, index) in &parsed_cfg.lang_index_map {
-        let is_default_lang = lang == default_lang;
-        let end_point = if is_default_lang {
-            "values/strings.xml".to_string()
-        } else {
-            format!("values-{}/strings.xml", lang)
-        };
+/// 获取string标签的name属性
+fn string_name(e: &BytesStart) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == XML_B_NAME)
+        .and_then(|attr| attr.unescape_value().ok().map(|v| v.to_string()))
+}
 
-        // 查找匹配的XML文件路径
-        let path = match paths.iter().find(|path| path.ends_with(&end_point)) {
-            Some(path) => path,
-            None => continue, // 没找到对应语言的文件，跳过这个语言
-        };
-        let path_index = PathIndex {
-            path: path.to_string(),
-            index: *index,
-            lang: lang.to_string(),
-        };
-        path_index_vec.push(path_index);
-    }
-    let lang_index_vec: Vec<u32> = path_index_vec.iter().map(|p| p.index).collect();
-
-    read_excel::process_excel_multi_lang(
-        &mut workbook,
-        &sheet_name,
-        tag_index,
-        lang_index_vec,
-        &mut tag_value_map,
-    )?;
-    println!("tag_value_map size: {}", tag_value_map.len());
-    if let Some(pos) = path_index_vec.iter().position(|p| &p.lang == default_lang) {
-        // 默认语言在生成时，已被移动到index 0
-        path_index_vec.swap(0, pos);
-    }
-
-    // 处理XML文件
-    path_index_vec.iter().for_each(|p| {
-        let index = p.index;
-        let path = &p.path;
-        let lang = &p.lang;
-        let mut write_map: HashMap<String, String> = HashMap::new();
-        tag_value_map.iter().for_each(|(tag, map)| {
-            write_map.insert(tag.to_string(), map.get(&index).unwrap().to_string());
-        });
-
-        let is_default_lang = lang == default_lang;
-        // 如果是默认语言，将tag_value_map的内容复制到default_valug_map
-        // 以便后续处理空值
-        let res = if is_default_lang && replace_blank_with_default {
-            // 处理默认语言,使用空default_valug_map
-            let res = update_xml_file(path, &write_map, &default_valug_map, &parsed_cfg);
-            // 处理完后，默认语言的值会被复制到default_valug_map
-            default_valug_map = write_map.clone();
-            res
-        } else {
-            update_xml_file(path, &write_map, &default_valug_map, &parsed_cfg)
-        };
-        write_map.clear(); // 清空map
-        if res.is_err() {
-            println!(
-                "更新XML文件失败,lang index: {}, err: {:?}",
-                index,
-                res.err()
-            );
+/// 还原Android字符串中的转义，如 \' \" \n \@
+pub fn unescape_android(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
         }
-    });
-    tag_value_map.clear(); // 清空map
-    tag_value_map.shrink_to_fit(); // 释放内存
-
-    Ok(())
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
 }
 
 /// 更新XML文件
@@ -252,22 +384,12 @@ fn update_xml_file(
     tag_value_map: &HashMap<String, String>,
     default_valug_map: &HashMap<String, String>,
     parsed_cfg: &ParsedCfg,
+    string_meta: &StringMeta,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // 创建临时文件路径
     let temp_path = format!("{}.temp", path);
     // 正则表达式
-    let regex_str = &parsed_cfg.regex;
-    let regex = if !is_blank(regex_str) {
-        match Regex::new(regex_str) {
-            Ok(regex) => Some(regex),
-            Err(e) => {
-                println!("正则表达式错误: {:?}", e);
-                None
-            }
-        }
-    } else {
-        None
-    };
+    let regex = build_regex(&parsed_cfg.regex);
 
     // 打开原始XML文件和临时文件
     let file = File::open(path)?;
@@ -281,6 +403,14 @@ fn update_xml_file(
     let mut buf = Vec::with_capacity(4 * 1024);
     let mut current_tag_name = None;
     let mut updated_tags = HashSet::new();
+    // 文件中已出现的name，以及重复的name - 出现次数
+    let mut seen_names = HashSet::new();
+    let mut duplicate_names: Vec<(String, usize)> = Vec::new();
+    let dedupe = parsed_cfg.duplicate_string == "dedupe";
+    // 更新的string上保留的其他属性 name - 属性
+    let mut preserved: Vec<(String, String)> = Vec::new();
+    // 标签之间的空白和注释延迟写入，以便删除重复的string前面的空白和注释，或替换string的注释
+    let mut pending: Vec<Event<'static>> = Vec::new();
 
     let replace_blank_with_default = parsed_cfg.replace_blank_with_default;
     let disable_escape = parsed_cfg.disable_escape;
@@ -300,11 +430,15 @@ fn update_xml_file(
         for (tag, value) in tag_value_map {
             // 添加换行和缩进
             xml_writer.write_event(Event::Text(BytesText::new(XML_INDENT)))?;
+            if let Some(comment) = string_meta.comment(tag) {
+                xml_writer.write_event(comment)?;
+                xml_writer.write_event(Event::Text(BytesText::new(XML_INDENT)))?;
+            }
 
             // 创建string标签
             let mut elem = BytesStart::new(XML_STRING);
             elem.push_attribute((XML_NAME, tag.as_str()));
-            xml_writer.write_event(Event::Start(elem))?;
+            xml_writer.write_event(Event::Start(string_meta.apply(&elem, tag)))?;
             let write_value =
                 get_write_value(tag, value, default_valug_map, replace_blank_with_default);
             write_text(
@@ -327,24 +461,49 @@ fn update_xml_file(
         loop {
             match xml_reader.read_event_into(&mut buf) {
                 Ok(Event::Start(ref e)) => {
+                    if e.name().as_ref() == XML_B_STRING
+                        && is_duplicate(e, &mut seen_names, &mut duplicate_names)
+                        && dedupe
+                    {
+                        // 删除重复的string及其前面的空白和工具写入的注释
+                        let end = e.to_end().into_owned();
+                        xml_reader.read_to_end_into(end.name(), &mut Vec::new())?;
+                        drop_attached(&mut pending);
+                        buf.clear();
+                        continue;
+                    }
                     if e.name().as_ref() == XML_B_STRING {
                         // 提取name属性
-                        for attr in e.attributes() {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == XML_B_NAME {
-                                    if let Ok(tag_name) = std::str::from_utf8(attr.value.as_ref()) {
-                                        if tag_value_map.contains_key(tag_name) {
-                                            current_tag_name = Some(tag_name.to_string());
-                                        }
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == XML_B_NAME {
+                                if let Ok(tag_name) = std::str::from_utf8(attr.value.as_ref()) {
+                                    if tag_value_map.contains_key(tag_name) {
+                                        current_tag_name = Some(tag_name.to_string());
                                     }
                                 }
                             }
                         }
                     }
-                    xml_writer.write_event(Event::Start(e.to_owned()))?;
+                    if let Some(comment) = current_tag_name
+                        .as_ref()
+                        .and_then(|tag| string_meta.comment(tag))
+                    {
+                        replace_attached(&mut pending, comment);
+                    }
+                    flush_pending(&mut xml_writer, &mut pending)?;
+                    match &current_tag_name {
+                        Some(tag) => {
+                            if let Some(attrs) = preserved_attributes(e) {
+                                preserved.push((tag.clone(), attrs));
+                            }
+                            xml_writer.write_event(Event::Start(string_meta.apply(e, tag)))?
+                        }
+                        None => xml_writer.write_event(Event::Start(e.to_owned()))?,
+                    }
                 }
 
                 Ok(Event::End(ref e)) => {
+                    flush_pending(&mut xml_writer, &mut pending)?;
                     if e.name().as_ref() == XML_B_RESOURCES {
                         // 在关闭resources标签前添加缺失的标签
                         add_missing_tags(
@@ -354,12 +513,31 @@ fn update_xml_file(
                             &updated_tags,
                             parsed_cfg,
                             &regex,
+                            string_meta,
                         )?;
                     }
                     xml_writer.write_event(Event::End(e.to_owned()))?;
                 }
 
+                Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == XML_B_STRING
+                        && is_duplicate(e, &mut seen_names, &mut duplicate_names)
+                        && dedupe =>
+                {
+                    drop_attached(&mut pending);
+                }
+
+                Ok(Event::Comment(e)) if current_tag_name.is_none() => {
+                    pending.push(Event::Comment(e.into_owned()));
+                }
+
                 Ok(Event::Text(e)) => {
+                    if current_tag_name.is_none() && is_blank(&e.unescape()?) {
+                        pending.push(Event::Text(e.into_owned()));
+                        buf.clear();
+                        continue;
+                    }
+                    flush_pending(&mut xml_writer, &mut pending)?;
                     // 提前处理没有当前标签的情况
                     if current_tag_name.is_none() {
                         xml_writer.write_event(Event::Text(e.to_owned()))?;
@@ -407,8 +585,14 @@ fn update_xml_file(
                     current_tag_name = None;
                 }
 
-                Ok(Event::Eof) => break,
-                Ok(e) => xml_writer.write_event(e)?,
+                Ok(Event::Eof) => {
+                    flush_pending(&mut xml_writer, &mut pending)?;
+                    break;
+                }
+                Ok(e) => {
+                    flush_pending(&mut xml_writer, &mut pending)?;
+                    xml_writer.write_event(e)?
+                }
                 Err(e) => return Err(e.into()),
             }
             buf.clear();
@@ -416,6 +600,22 @@ fn update_xml_file(
     }
     drop(xml_writer);
     drop(xml_reader);
+    if parsed_cfg.duplicate_string != "off" {
+        let action = if dedupe {
+            "已保留第一个，删除其余"
+        } else {
+            "aapt2会拒绝该文件"
+        };
+        for (name, count) in &duplicate_names {
+            println!(
+                "[重复string] {}: {} 出现{}次，{}",
+                path, name, count, action
+            );
+        }
+    }
+    for (name, attrs) in &preserved {
+        println!("[保留属性] {}: {} {}", path, name, attrs);
+    }
     // 替换原文件
     remove_file(path)?;
     rename(temp_path, path)?;
@@ -423,6 +623,103 @@ fn update_xml_file(
     Ok(())
 }
 
+/// string上除name和标记以外的属性，如 tools:ignore，没有时返回None
+fn preserved_attributes(e: &BytesStart) -> Option<String> {
+    let attrs: Vec<String> = e
+        .attributes()
+        .flatten()
+        .filter(|attr| {
+            ![XML_B_NAME, XML_B_TRANSLATABLE, XML_B_FORMATTED].contains(&attr.key.as_ref())
+        })
+        .map(|attr| {
+            format!(
+                "{}=\"{}\"",
+                String::from_utf8_lossy(attr.key.as_ref()),
+                String::from_utf8_lossy(&attr.value)
+            )
+        })
+        .collect();
+    (!attrs.is_empty()).then(|| attrs.join(" "))
+}
+
+/// 写入延迟的空白和注释
+fn flush_pending(
+    xml_writer: &mut Writer<BufWriter<File>>,
+    pending: &mut Vec<Event<'static>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for event in pending.drain(..) {
+        xml_writer.write_event(event)?;
+    }
+    Ok(())
+}
+
+/// 是否为工具写入的注释
+fn is_marked_comment(comment: &[u8]) -> bool {
+    String::from_utf8_lossy(comment)
+        .trim_start()
+        .starts_with(COMMENT_MARKER)
+}
+
+/// 紧贴在string前面的工具写入的注释位置，注释与string之间只有一个换行时视为string的注释
+fn attached_comment(pending: &[Event]) -> Option<usize> {
+    let index = pending
+        .iter()
+        .rposition(|event| matches!(event, Event::Comment(_)))?;
+    match &pending[index] {
+        Event::Comment(comment) if is_marked_comment(comment) => {}
+        _ => return None,
+    }
+    let newlines: usize = pending[index + 1..]
+        .iter()
+        .map(|event| match event {
+            Event::Text(text) => text.iter().filter(|b| **b == b'\n').count(),
+            _ => 0,
+        })
+        .sum();
+    (newlines <= 1).then_some(index)
+}
+
+/// 替换string的注释，没有工具写入的注释时添加在其他注释之后、string之前
+fn replace_attached(pending: &mut Vec<Event<'static>>, comment: Event<'static>) {
+    match attached_comment(pending) {
+        Some(index) => pending[index] = comment,
+        None => {
+            pending.push(comment);
+            pending.push(Event::Text(BytesText::new(XML_INDENT)));
+        }
+    }
+}
+
+/// 丢弃string前面的空白和工具写入的注释，其他注释保留
+fn drop_attached(pending: &mut Vec<Event<'static>>) {
+    if let Some(index) = attached_comment(pending) {
+        pending.truncate(index);
+    }
+    while matches!(pending.last(), Some(Event::Text(_))) {
+        pending.pop();
+    }
+}
+
+/// 记录string的name，已出现过时返回true，并累计重复次数
+fn is_duplicate(
+    e: &BytesStart,
+    seen_names: &mut HashSet<String>,
+    duplicate_names: &mut Vec<(String, usize)>,
+) -> bool {
+    let name = match string_name(e) {
+        Some(name) => name,
+        None => return false,
+    };
+    if seen_names.insert(name.clone()) {
+        return false;
+    }
+    match duplicate_names.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, count)) => *count += 1,
+        None => duplicate_names.push((name, 2)),
+    }
+    true
+}
+
 /// 添加缺失的标签
 fn add_missing_tags(
     xml_writer: &mut Writer<BufWriter<File>>,
@@ -431,6 +728,7 @@ fn add_missing_tags(
     updated_tags: &HashSet<String>,
     parsed_cfg: &ParsedCfg,
     regex: &Option<Regex>,
+    string_meta: &StringMeta,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let disable_escape = parsed_cfg.disable_escape;
     let escape_only = &parsed_cfg.escape_only;
@@ -443,12 +741,16 @@ fn add_missing_tags(
             }
             // 为每个新标签添加缩进
             xml_writer.write_event(Event::Text(BytesText::new(XML_INDENT)))?;
+            if let Some(comment) = string_meta.comment(tag) {
+                xml_writer.write_event(comment)?;
+                xml_writer.write_event(Event::Text(BytesText::new(XML_INDENT)))?;
+            }
 
             // 创建新标签
             let mut elem = BytesStart::new(XML_STRING);
             elem.push_attribute((XML_NAME, tag.as_str()));
 
-            xml_writer.write_event(Event::Start(elem))?;
+            xml_writer.write_event(Event::Start(string_meta.apply(&elem, tag)))?;
             let write_value = get_write_value(
                 tag,
                 value,
@@ -470,39 +772,76 @@ fn write_text(
     disable_escape: bool,
     xml_writer: &mut Writer<BufWriter<File>>,
     value: &str,
-    escape_only: &Vec<(String, String)>,
+    escape_only: &[(String, String)],
     regex: &Option<Regex>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let value = if let Some(regex) = regex {
-        // 使用正则表达式替换
-        regex.replace_all(value, "").to_string()
-    } else {
-        value.to_string()
-    };
+    let value = clean_value(value, regex);
+    let escaped_value = escape_text(disable_escape, &value, escape_only);
+    // 内容已转义，直接写入
+    xml_writer.write_event(Event::Text(BytesText::from_escaped(escaped_value)))?;
+    Ok(())
+}
+
+/// 转义文本，返回写入strings.xml的最终内容
+pub fn escape_text(disable_escape: bool, value: &str, escape_only: &[(String, String)]) -> String {
     if disable_escape {
         // 直接写入文本内容，不会再自动转义
-        xml_writer.write_event(Event::Text(BytesText::from_escaped(value)))?;
+        value.to_string()
+    } else if escape_only.is_empty() {
+        // 转义所有内容
+        quick_xml::escape::escape(value).to_string()
     } else {
-        if escape_only.is_empty() {
-            // 转义所有内容
-            xml_writer.write_event(Event::Text(BytesText::new(&value)))?;
-        } else {
-            // 只转义指定的内容
-            let mut escaped_value = value.to_string();
-            for (key, val) in escape_only {
-                escaped_value = escaped_value.replace(key, val);
-            }
-            xml_writer.write_event(Event::Text(BytesText::from_escaped(&escaped_value)))?;
+        // 只转义指定的内容
+        let mut escaped_value = value.to_string();
+        for (key, val) in escape_only {
+            escaped_value = escaped_value.replace(key, val);
+        }
+        escaped_value
+    }
+}
+
+/// 根据配置构建正则表达式，为空或无效时返回None
+pub fn build_regex(regex_str: &str) -> Option<Regex> {
+    if is_blank(regex_str) {
+        return None;
+    }
+    match Regex::new(regex_str) {
+        Ok(regex) => Some(regex),
+        Err(e) => {
+            println!("正则表达式错误: {:?}", e);
+            None
         }
     }
+}
+
+/**
+ * 先写入临时文件再替换原文件，写入中途出错时原文件保持不变
+ * @param path 文件路径，文件可以尚不存在
+ * @param content 文件内容
+ */
+pub fn write_file_atomic(path: &Path, content: &str) -> Result<(), Box<dyn Error>> {
+    let temp_path = format!("{}.temp", path.display());
+    std::fs::write(&temp_path, content)?;
+    if path.exists() {
+        remove_file(path)?;
+    }
+    rename(temp_path, path)?;
     Ok(())
 }
 
-fn is_blank(value: &str) -> bool {
+/// 使用正则表达式清理文本，匹配的内容会被移除
+pub fn clean_value(value: &str, regex: &Option<Regex>) -> String {
+    match regex {
+        Some(regex) => regex.replace_all(value, "").to_string(),
+        None => value.to_string(),
+    }
+}
+
+pub fn is_blank(value: &str) -> bool {
     value.trim().is_empty()
 }
 
-fn get_write_value<'a>(
+pub fn get_write_value<'a>(
     tag: &String,
     value: &'a String,
     default_valug_map: &'a HashMap<String, String>,
@@ -518,3 +857,82 @@ fn get_write_value<'a>(
         value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRINGS_XML: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!-- header -->\n<resources>\n\n    <string name=\"hello\">Old</string>\n    <!-- again -->\n    <string name=\"hello\">Old 2</string>\n\n\t<string name=\"keep\">Keep</string>  <!-- same line -->\n</resources>\n<!-- trailing -->\n";
+
+    /**
+     * 将hello更新为New，返回更新后的内容
+     * @param content 原有的strings.xml
+     * @param duplicate_string 重复name的处理
+     * @param comment hello的备注
+     */
+    fn update(content: &str, duplicate_string: &str, comment: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("excel_to_xml_test_xml_{}", duplicate_string));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("strings.xml");
+        std::fs::write(&path, content).unwrap();
+
+        let cfg = format!(
+            r#"{{"tagName":"tag","defaultLang":"en","langMap":{{"en":"English"}},"escapeOnly":{{}},"duplicateString":"{}"}}"#,
+            duplicate_string
+        );
+        let parsed_cfg = ParsedCfg::from_json(&cfg).unwrap();
+        let tag_value_map: HashMap<String, String> = [("hello".to_string(), "New".to_string())]
+            .into_iter()
+            .collect();
+        let mut meta = SheetMeta::default();
+        meta.comment_map
+            .insert("hello".to_string(), comment.to_string());
+        let string_meta = StringMeta {
+            meta: &meta,
+            is_default: true,
+        };
+        let path = path.to_str().unwrap();
+        update_xml_file(
+            path,
+            &tag_value_map,
+            &HashMap::new(),
+            &parsed_cfg,
+            &string_meta,
+        )
+        .unwrap();
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_update_xml_file() {
+        // 只修改string的内容，空白、注释和resources之后的内容保持不变
+        assert_eq!(
+            update(STRINGS_XML, "warn", ""),
+            STRINGS_XML.replace("Old 2", "New").replace("Old", "New")
+        );
+        // 删除重复的string，手写的注释保留
+        assert_eq!(
+            update(STRINGS_XML, "dedupe", ""),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!-- header -->\n<resources>\n\n    <string name=\"hello\">New</string>\n    <!-- again -->\n\n\t<string name=\"keep\">Keep</string>  <!-- same line -->\n</resources>\n<!-- trailing -->\n"
+        );
+    }
+
+    #[test]
+    fn test_string_comment() {
+        // 只替换带标记的注释，手写的注释保留，备注写在其后
+        let content = "<resources>\n    <!-- note -->\n    <!-- [excel_to_xml] old -->\n    <string name=\"hello\">Old</string>\n    <!-- other -->\n    <string name=\"keep\">Keep</string>\n</resources>\n";
+        let expected = "<resources>\n    <!-- note -->\n    <!-- [excel_to_xml] a- - -b- -->\n    <string name=\"hello\">New</string>\n    <!-- other -->\n    <string name=\"keep\">Keep</string>\n</resources>\n";
+        assert_eq!(update(content, "off", "a---b-"), expected);
+        // 再次同步时不重复添加
+        assert_eq!(update(expected, "off", "a---b-"), expected);
+
+        let content = "<resources>\n    <!-- note -->\n    <string name=\"hello\">Old</string>\n</resources>\n";
+        assert_eq!(
+            update(content, "off", "Greeting"),
+            "<resources>\n    <!-- note -->\n    <!-- [excel_to_xml] Greeting -->\n    <string name=\"hello\">New</string>\n</resources>\n"
+        );
+
+        assert_eq!(escape_comment("a---b--"), "a- - -b- -");
+        assert!(!escape_comment("----").contains("--"));
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::File,
+    io::{BufReader, Read},
+};
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use zip::ZipArchive;
+
+/// xlsx压缩包
+pub type XlsxArchive = ZipArchive<BufReader<File>>;
+
+const WORKBOOK_PART: &str = "xl/workbook.xml";
+const WORKBOOK_RELS_PART: &str = "xl/_rels/workbook.xml.rels";
+const STYLES_PART: &str = "xl/styles.xml";
+const B_SHEET: &[u8] = b"sheet";
+const B_RELATIONSHIP: &[u8] = b"Relationship";
+
+/// 打开xlsx压缩包
+pub fn open_package(path: &str) -> Result<XlsxArchive, Box<dyn Error>> {
+    Ok(ZipArchive::new(BufReader::new(File::open(path)?))?)
+}
+
+/// 读取压缩包中的文件内容
+pub fn read_part(archive: &mut XlsxArchive, name: &str) -> Result<String, Box<dyn Error>> {
+    let mut content = String::new();
+    archive.by_name(name)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/**
+ * 查找工作表对应的xml文件路径
+ * @param archive xlsx压缩包
+ * @param sheet_name 工作表名称，为空时使用第一个工作表
+ * @return 压缩包中的路径，如 xl/worksheets/sheet1.xml
+ */
+pub fn sheet_xml_path(
+    archive: &mut XlsxArchive,
+    sheet_name: &str,
+) -> Result<String, Box<dyn Error>> {
+    let workbook = read_part(archive, WORKBOOK_PART)?;
+    let mut xml_reader = Reader::from_str(&workbook);
+    let mut rel_id = None;
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == B_SHEET => {
+                let name = attr_value(&e, b"name").unwrap_or_default();
+                if sheet_name.is_empty() || name == sheet_name {
+                    rel_id = attr_value(&e, b"r:id");
+                    break;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let rel_id = rel_id.ok_or_else(|| format!("未找到工作表: {}", sheet_name))?;
+
+    let rels = read_part(archive, WORKBOOK_RELS_PART)?;
+    let mut xml_reader = Reader::from_str(&rels);
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == B_RELATIONSHIP
+                    && attr_value(&e, b"Id").as_deref() == Some(rel_id.as_str()) =>
+            {
+                let target = attr_value(&e, b"Target").unwrap_or_default();
+                // Target可以是相对xl/的路径，也可以是绝对路径
+                return Ok(match target.strip_prefix('/') {
+                    Some(path) => path.to_string(),
+                    None => format!("xl/{}", target),
+                });
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Err(format!("未找到工作表文件: {}", sheet_name).into())
+}
+
+/// 获取属性值
+pub fn attr_value(e: &BytesStart, key: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == key)
+        .and_then(|attr| attr.unescape_value().ok().map(|v| v.to_string()))
+}
+
+/// 解析单元格引用，B5 -> (4, 1)，行列均从0开始
+pub fn cell_position(cell_ref: &str) -> Option<(u32, u32)> {
+    let split = cell_ref.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell_ref.split_at(split);
+    if letters.is_empty() {
+        return None;
+    }
+    let mut col: u32 = 0;
+    for c in letters.chars() {
+        if !c.is_ascii_uppercase() {
+            return None;
+        }
+        col = col * 26 + (c as u32 - 'A' as u32 + 1);
+    }
+    let row: u32 = digits.parse().ok()?;
+    Some((row.checked_sub(1)?, col - 1))
+}
+
+/// 获取row的行号，从0开始
+pub fn row_index(e: &BytesStart) -> Option<u32> {
+    attr_value(e, b"r")
+        .and_then(|r| r.parse::<u32>().ok())
+        .and_then(|r| r.checked_sub(1))
+}
+
+/// 工作表中隐藏的行、列和删除线单元格，行列均从0开始
+#[derive(Debug, Default, PartialEq)]
+pub struct SheetVisibility {
+    pub hidden_rows: HashSet<u32>,
+    pub hidden_cols: HashSet<u32>,
+    pub struck_cells: HashSet<(u32, u32)>,
+}
+
+/**
+ * 读取工作表中隐藏的行、列和使用删除线格式的单元格
+ * 删除线只检查单元格格式，不检查富文本中的部分文字
+ * @param path xlsx文件路径
+ * @param sheet_name 工作表名称，为空时使用第一个工作表
+ * @return 隐藏和删除线信息
+ */
+pub fn read_sheet_visibility(
+    path: &str,
+    sheet_name: &str,
+) -> Result<SheetVisibility, Box<dyn Error>> {
+    let mut archive = open_package(path)?;
+    let struck_styles = match read_part(&mut archive, STYLES_PART) {
+        Ok(styles) => struck_styles(&styles)?,
+        Err(_) => HashSet::new(), // 没有样式表
+    };
+    let sheet_path = sheet_xml_path(&mut archive, sheet_name)?;
+    let sheet_xml = read_part(&mut archive, &sheet_path)?;
+    parse_visibility(&sheet_xml, &struck_styles)
+}
+
+/// 解析styles.xml，返回字体带删除线的单元格样式序号
+fn struck_styles(styles: &str) -> Result<HashSet<usize>, Box<dyn Error>> {
+    let mut xml_reader = Reader::from_str(styles);
+    let mut struck_fonts = Vec::new();
+    let mut result = HashSet::new();
+    let mut in_fonts = false;
+    let mut in_cell_xfs = false;
+    let mut xf_index = 0;
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"fonts" => in_fonts = true,
+                b"cellXfs" => in_cell_xfs = true,
+                b"font" if in_fonts => struck_fonts.push(false),
+                b"xf" if in_cell_xfs => {
+                    xf_struck(&e, &struck_fonts, xf_index, &mut result);
+                    xf_index += 1;
+                }
+                _ => {}
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"font" if in_fonts => struck_fonts.push(false),
+                // <strike/> 或 <strike val="1"/>
+                b"strike" if in_fonts => {
+                    let val = attr_value(&e, b"val");
+                    if let Some(last) = struck_fonts.last_mut() {
+                        *last = !matches!(val.as_deref(), Some("0") | Some("false"));
+                    }
+                }
+                b"xf" if in_cell_xfs => {
+                    xf_struck(&e, &struck_fonts, xf_index, &mut result);
+                    xf_index += 1;
+                }
+                _ => {}
+            },
+            Event::End(e) => match e.local_name().as_ref() {
+                b"fonts" => in_fonts = false,
+                b"cellXfs" => in_cell_xfs = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+/// 单元格样式使用的字体带删除线时记录样式序号
+fn xf_struck(e: &BytesStart, struck_fonts: &[bool], xf_index: usize, result: &mut HashSet<usize>) {
+    let font_id = attr_value(e, b"fontId").and_then(|id| id.parse::<usize>().ok());
+    if font_id.is_some_and(|id| struck_fonts.get(id) == Some(&true)) {
+        result.insert(xf_index);
+    }
+}
+
+/// 解析工作表xml中的隐藏行、隐藏列和单元格样式
+fn parse_visibility(
+    sheet_xml: &str,
+    struck_styles: &HashSet<usize>,
+) -> Result<SheetVisibility, Box<dyn Error>> {
+    let mut xml_reader = Reader::from_str(sheet_xml);
+    let mut visibility = SheetVisibility::default();
+    let mut cur_row: Option<u32> = None;
+    let mut next_col: u32 = 0;
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"row" => {
+                    let row = row_index(&e).unwrap_or(cur_row.map_or(0, |r| r + 1));
+                    cur_row = Some(row);
+                    next_col = 0;
+                    if is_hidden(&e) {
+                        visibility.hidden_rows.insert(row);
+                    }
+                }
+                // <col min="2" max="3" hidden="1"/>，min和max从1开始
+                b"col" if is_hidden(&e) => {
+                    let min = attr_value(&e, b"min").and_then(|v| v.parse::<u32>().ok());
+                    let max = attr_value(&e, b"max").and_then(|v| v.parse::<u32>().ok());
+                    if let (Some(min), Some(max)) = (min, max) {
+                        visibility
+                            .hidden_cols
+                            .extend((min.max(1)..=max).map(|col| col - 1));
+                    }
+                }
+                b"c" => {
+                    let (row, col) = attr_value(&e, b"r")
+                        .and_then(|r| cell_position(&r))
+                        .unwrap_or((cur_row.unwrap_or(0), next_col));
+                    next_col = col + 1;
+                    let style = attr_value(&e, b"s").and_then(|s| s.parse::<usize>().ok());
+                    if style.is_some_and(|s| struck_styles.contains(&s)) {
+                        visibility.struck_cells.insert((row, col));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(visibility)
+}
+
+/// 合并单元格区域，行列均从0开始，包含结束的行列
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MergedRegion {
+    pub first_row: u32,
+    pub first_col: u32,
+    pub last_row: u32,
+    pub last_col: u32,
+}
+
+impl MergedRegion {
+    /// 是否包含单元格
+    pub fn contains(&self, row: u32, col: u32) -> bool {
+        (self.first_row..=self.last_row).contains(&row)
+            && (self.first_col..=self.last_col).contains(&col)
+    }
+}
+
+/**
+ * 读取工作表中的合并单元格区域
+ * @param path xlsx文件路径
+ * @param sheet_name 工作表名称，为空时使用第一个工作表
+ * @return 合并单元格区域
+ */
+pub fn read_merged_regions(
+    path: &str,
+    sheet_name: &str,
+) -> Result<Vec<MergedRegion>, Box<dyn Error>> {
+    let mut archive = open_package(path)?;
+    let sheet_path = sheet_xml_path(&mut archive, sheet_name)?;
+    let sheet_xml = read_part(&mut archive, &sheet_path)?;
+    parse_merged_regions(&sheet_xml)
+}
+
+/// 解析工作表xml中的 <mergeCell ref="A2:A4"/>
+fn parse_merged_regions(sheet_xml: &str) -> Result<Vec<MergedRegion>, Box<dyn Error>> {
+    let mut xml_reader = Reader::from_str(sheet_xml);
+    let mut regions = Vec::new();
+    loop {
+        match xml_reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"mergeCell" => {
+                let range = attr_value(&e, b"ref").unwrap_or_default();
+                let positions = range
+                    .split_once(':')
+                    .and_then(|(first, last)| Some((cell_position(first)?, cell_position(last)?)));
+                if let Some(((first_row, first_col), (last_row, last_col))) = positions {
+                    regions.push(MergedRegion {
+                        first_row,
+                        first_col,
+                        last_row,
+                        last_col,
+                    });
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(regions)
+}
+
+/// hidden属性为1或true
+fn is_hidden(e: &BytesStart) -> bool {
+    matches!(
+        attr_value(e, b"hidden").as_deref(),
+        Some("1") | Some("true")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_visibility() {
+        let styles = r#"<styleSheet><fonts count="3"><font><sz val="11"/></font><font><strike/></font><font><strike val="0"/></font></fonts><cellStyleXfs count="1"><xf fontId="1"/></cellStyleXfs><cellXfs count="3"><xf fontId="0"/><xf fontId="2"/><xf fontId="1" applyFont="1"/></cellXfs></styleSheet>"#;
+        let struck = struck_styles(styles).unwrap();
+        assert_eq!(struck, HashSet::from([2]));
+
+        let sheet = r#"<worksheet><cols><col min="2" max="3" hidden="1"/></cols><sheetData><row r="1"><c r="A1" s="2"/></row><row r="3" hidden="1"><c r="B3" s="1"/></row><row><c s="2"/><c s="2"/></row></sheetData></worksheet>"#;
+        let visibility = parse_visibility(sheet, &struck).unwrap();
+        assert_eq!(visibility.hidden_rows, HashSet::from([2]));
+        assert_eq!(visibility.hidden_cols, HashSet::from([1, 2]));
+        assert_eq!(
+            visibility.struck_cells,
+            HashSet::from([(0, 0), (3, 0), (3, 1)])
+        );
+    }
+
+    #[test]
+    fn test_parse_merged_regions() {
+        let sheet = r#"<worksheet><sheetData/><mergeCells count="3"><mergeCell ref="C2:C4"/><mergeCell ref="B5:D5"/><mergeCell ref="A1"/></mergeCells></worksheet>"#;
+        let regions = parse_merged_regions(sheet).unwrap();
+        assert_eq!(regions.len(), 2);
+        assert!(regions[0].contains(3, 2));
+        assert!(!regions[0].contains(4, 2));
+        assert!(regions[1].contains(4, 3));
+    }
+}